@@ -14,9 +14,24 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+pub mod apic;
+pub mod context;
+pub mod cpuid;
+pub mod decoder;
+pub mod exception;
+pub mod fixup;
 pub mod gdt;
+pub mod hpet;
 pub mod idt;
 pub mod instruction;
+pub mod ioapic;
+pub mod paging;
+pub mod pic;
+pub mod pit;
+pub mod port;
+pub mod ps2;
 pub mod register;
+pub mod rtc;
 pub mod serial;
+pub mod trap;
 pub mod tss;
@@ -0,0 +1,307 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::ptr;
+
+use super::instruction;
+
+pub const BASE_MSR: u32 = 0x1B;
+pub const TSC_DEADLINE_MSR: u32 = 0x6E0;
+
+const BASE_BOOTSTRAP: u64 = 1 << 8;
+const BASE_X2APIC: u64 = 1 << 10;
+const BASE_ENABLE: u64 = 1 << 11;
+const BASE_ADDRESS_MASK: u64 = 0x000F_FFFF_FFFF_F000;
+
+const ID: u32 = 0x20;
+const VERSION: u32 = 0x30;
+const TASK_PRIORITY: u32 = 0x80;
+const END_OF_INTERRUPT: u32 = 0xB0;
+const SPURIOUS: u32 = 0xF0;
+const ERROR_STATUS: u32 = 0x280;
+const INTERRUPT_COMMAND: u32 = 0x300;
+const INTERRUPT_COMMAND_HIGH: u32 = 0x310;
+const TIMER: u32 = 0x320;
+const THERMAL: u32 = 0x330;
+const PERFORMANCE: u32 = 0x340;
+const LINT0: u32 = 0x350;
+const LINT1: u32 = 0x360;
+const ERROR: u32 = 0x370;
+const INITIAL_COUNT: u32 = 0x380;
+const CURRENT_COUNT: u32 = 0x390;
+const DIVIDE_CONFIGURATION: u32 = 0x3E0;
+
+const SOFTWARE_ENABLE: u32 = 1 << 8;
+const MASKED: u32 = 1 << 16;
+const DELIVERY_PENDING: u32 = 1 << 12;
+const LEVEL_ASSERT: u32 = 1 << 14;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    XApic { base: u64 },
+    X2Apic,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TimerMode {
+    OneShot = 0b00,
+    Periodic = 0b01,
+    TscDeadline = 0b10,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Divide {
+    By1 = 0b1011,
+    By2 = 0b0000,
+    By4 = 0b0001,
+    By8 = 0b0010,
+    By16 = 0b0011,
+    By32 = 0b1000,
+    By64 = 0b1001,
+    By128 = 0b1010,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    Fixed(u8),
+    LowestPriority(u8),
+    SystemManagement,
+    NonMaskable,
+    Init,
+    Startup(u8),
+}
+
+impl Delivery {
+    fn bits(self) -> u32 {
+        match self {
+            Self::Fixed(vector) => u32::from(vector),
+            Self::LowestPriority(vector) => (0b001 << 8) | u32::from(vector),
+            Self::SystemManagement => 0b010 << 8,
+            Self::NonMaskable => 0b100 << 8,
+            Self::Init => (0b101 << 8) | LEVEL_ASSERT,
+            Self::Startup(page) => (0b110 << 8) | LEVEL_ASSERT | u32::from(page),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Destination {
+    Single(u32),
+    Current,
+    All,
+    Others,
+}
+
+impl Destination {
+    fn shorthand(self) -> u32 {
+        match self {
+            Self::Single(_) => 0b00 << 18,
+            Self::Current => 0b01 << 18,
+            Self::All => 0b10 << 18,
+            Self::Others => 0b11 << 18,
+        }
+    }
+
+    fn id(self) -> u32 {
+        match self {
+            Self::Single(id) => id,
+            _ => 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct LocalApic {
+    mode: Mode,
+}
+
+impl LocalApic {
+    #[must_use]
+    pub const fn new(mode: Mode) -> Self {
+        Self { mode }
+    }
+
+    #[must_use]
+    pub fn physical_base() -> u64 {
+        instruction::rdmsr(BASE_MSR) & BASE_ADDRESS_MASK
+    }
+
+    #[must_use]
+    pub fn is_bootstrap() -> bool {
+        instruction::rdmsr(BASE_MSR) & BASE_BOOTSTRAP != 0
+    }
+
+    #[must_use]
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    fn read(&self, register: u32) -> u32 {
+        match self.mode {
+            Mode::XApic { base } => unsafe {
+                ptr::read_volatile((base + u64::from(register)) as *const u32)
+            },
+            Mode::X2Apic => (instruction::rdmsr(0x800 + (register >> 4)) & 0xFFFF_FFFF) as u32,
+        }
+    }
+
+    fn write(&self, register: u32, value: u32) {
+        match self.mode {
+            Mode::XApic { base } => unsafe {
+                ptr::write_volatile((base + u64::from(register)) as *mut u32, value);
+            },
+            Mode::X2Apic => instruction::wrmsr(0x800 + (register >> 4), u64::from(value)),
+        }
+    }
+
+    pub fn enable(&self, spurious_vector: u8) {
+        let mut base = instruction::rdmsr(BASE_MSR) | BASE_ENABLE;
+
+        if self.mode == Mode::X2Apic {
+            base |= BASE_X2APIC;
+        }
+
+        instruction::wrmsr(BASE_MSR, base);
+
+        self.write(TASK_PRIORITY, 0);
+        self.write(SPURIOUS, SOFTWARE_ENABLE | u32::from(spurious_vector));
+    }
+
+    pub fn disable(&self) {
+        let spurious = self.read(SPURIOUS);
+        self.write(SPURIOUS, spurious & !SOFTWARE_ENABLE);
+    }
+
+    #[must_use]
+    pub fn id(&self) -> u32 {
+        match self.mode {
+            Mode::XApic { .. } => self.read(ID) >> 24,
+            Mode::X2Apic => self.read(ID),
+        }
+    }
+
+    #[must_use]
+    pub fn version(&self) -> u8 {
+        (self.read(VERSION) & 0xFF) as u8
+    }
+
+    #[must_use]
+    pub fn max_lvt_entry(&self) -> u8 {
+        ((self.read(VERSION) >> 16) & 0xFF) as u8
+    }
+
+    pub fn end_of_interrupt(&self) {
+        self.write(END_OF_INTERRUPT, 0);
+    }
+
+    pub fn set_error_vector(&self, vector: u8) {
+        self.write(ERROR, u32::from(vector));
+    }
+
+    #[must_use]
+    pub fn error_status(&self) -> u32 {
+        self.write(ERROR_STATUS, 0);
+        self.read(ERROR_STATUS)
+    }
+
+    pub fn mask_local_interrupts(&self) {
+        for register in [THERMAL, PERFORMANCE, LINT0] {
+            self.write(register, MASKED);
+        }
+
+        self.write(LINT1, Delivery::NonMaskable.bits());
+    }
+
+    pub fn set_timer(&self, vector: u8, mode: TimerMode, divide: Divide) {
+        self.write(DIVIDE_CONFIGURATION, divide as u32);
+        self.write(TIMER, ((mode as u32) << 17) | u32::from(vector));
+    }
+
+    pub fn mask_timer(&self) {
+        let timer = self.read(TIMER);
+        self.write(TIMER, timer | MASKED);
+    }
+
+    pub fn unmask_timer(&self) {
+        let timer = self.read(TIMER);
+        self.write(TIMER, timer & !MASKED);
+    }
+
+    pub fn set_initial_count(&self, count: u32) {
+        self.write(INITIAL_COUNT, count);
+    }
+
+    #[must_use]
+    pub fn current_count(&self) -> u32 {
+        self.read(CURRENT_COUNT)
+    }
+
+    pub fn set_deadline(&self, deadline: u64) {
+        instruction::wrmsr(TSC_DEADLINE_MSR, deadline);
+    }
+
+    pub fn stop_timer(&self) {
+        self.set_initial_count(0);
+    }
+
+    pub fn send_ipi(&self, destination: Destination, delivery: Delivery) {
+        let command = destination.shorthand() | delivery.bits();
+
+        match self.mode {
+            Mode::XApic { .. } => {
+                self.write(INTERRUPT_COMMAND_HIGH, destination.id() << 24);
+                self.write(INTERRUPT_COMMAND, command);
+
+                while self.read(INTERRUPT_COMMAND) & DELIVERY_PENDING != 0 {
+                    core::hint::spin_loop();
+                }
+            }
+            Mode::X2Apic => {
+                let value = (u64::from(destination.id()) << 32) | u64::from(command);
+                instruction::wrmsr(0x800 + (INTERRUPT_COMMAND >> 4), value);
+            }
+        }
+    }
+
+    pub fn send_init(&self, id: u32) {
+        self.send_ipi(Destination::Single(id), Delivery::Init);
+    }
+
+    pub fn send_startup(&self, id: u32, page: u8) {
+        self.send_ipi(Destination::Single(id), Delivery::Startup(page));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delivery_bits() {
+        assert_eq!(Delivery::Fixed(0x40).bits(), 0x0040);
+        assert_eq!(Delivery::Init.bits(), 0x4500);
+        assert_eq!(Delivery::Startup(0x08).bits(), 0x4608);
+        assert_eq!(Delivery::NonMaskable.bits(), 0x0400);
+    }
+
+    #[test]
+    fn test_destination_shorthand() {
+        assert_eq!(Destination::Single(1).shorthand(), 0x00000);
+        assert_eq!(Destination::Current.shorthand(), 0x40000);
+        assert_eq!(Destination::All.shorthand(), 0x80000);
+        assert_eq!(Destination::Others.shorthand(), 0xC0000);
+    }
+}
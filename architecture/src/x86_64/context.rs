@@ -0,0 +1,183 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::arch::{asm, global_asm};
+
+pub type Entry = extern "C" fn(usize) -> !;
+
+const CALLEE_SAVED: usize = 6;
+
+#[derive(Clone, Copy)]
+#[repr(C, align(16))]
+pub struct FpuState([u8; 512]);
+
+impl Default for FpuState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FpuState {
+    #[must_use]
+    pub const fn new() -> Self {
+        let mut area = [0; 512];
+
+        let control = 0x037Fu16.to_le_bytes();
+        area[0] = control[0];
+        area[1] = control[1];
+
+        let mxcsr = 0x1F80u32.to_le_bytes();
+        area[24] = mxcsr[0];
+        area[25] = mxcsr[1];
+        area[26] = mxcsr[2];
+        area[27] = mxcsr[3];
+
+        Self(area)
+    }
+
+    pub fn save(&mut self) {
+        unsafe {
+            asm!("fxsave64 [{0}]", in(reg) self.0.as_mut_ptr());
+        }
+    }
+
+    pub fn restore(&self) {
+        unsafe {
+            asm!("fxrstor64 [{0}]", in(reg) self.0.as_ptr());
+        }
+    }
+}
+
+unsafe extern "C" {
+    fn context_switch(previous: *mut u64, next: u64);
+    fn context_start();
+}
+
+global_asm!(
+    r#"
+    .section .text.context, "ax"
+    .global context_switch
+    context_switch:
+        push rbp
+        push rbx
+        push r12
+        push r13
+        push r14
+        push r15
+        mov [rdi], rsp
+        mov rsp, rsi
+        pop r15
+        pop r14
+        pop r13
+        pop r12
+        pop rbx
+        pop rbp
+        ret
+
+    .global context_start
+    context_start:
+        mov rdi, r13
+        call r12
+        ud2
+    .previous
+    "#
+);
+
+/// # Safety
+///
+/// `top` must be the 16-byte aligned top of a writable stack that outlives the context.
+#[must_use]
+pub unsafe fn prepare(top: u64, entry: Entry, argument: usize) -> u64 {
+    let frame = [
+        0,
+        0,
+        argument as u64,
+        entry as *const () as u64,
+        0,
+        0,
+        context_start as *const () as u64,
+    ];
+    let stack_pointer = top - (size_of_val(&frame) as u64);
+
+    unsafe {
+        (stack_pointer as *mut [u64; CALLEE_SAVED + 1]).write(frame);
+    }
+
+    stack_pointer
+}
+
+/// # Safety
+///
+/// `previous` must be writable and `next` a live stack pointer from [`prepare`] or a switch.
+pub unsafe fn switch_to(previous: *mut u64, next: u64) {
+    unsafe {
+        context_switch(previous, next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::boxed::Box;
+
+    static mut MAIN: u64 = 0;
+    static mut THREAD: u64 = 0;
+    static mut VALUE: usize = 0;
+
+    #[repr(C, align(16))]
+    struct Stack([u8; 16384]);
+
+    extern "C" fn entry(argument: usize) -> ! {
+        unsafe {
+            VALUE = argument;
+            switch_to(&raw mut THREAD, MAIN);
+
+            VALUE += 1;
+            switch_to(&raw mut THREAD, MAIN);
+        }
+
+        unreachable!()
+    }
+
+    #[test]
+    fn test_switch_to() {
+        let mut stack = Box::new(Stack([0; 16384]));
+        let top = stack.0.as_mut_ptr() as u64 + stack.0.len() as u64;
+
+        unsafe {
+            THREAD = prepare(top, entry, 41);
+            assert_eq!((&raw const THREAD).read(), top - 56);
+
+            switch_to(&raw mut MAIN, THREAD);
+            assert_eq!((&raw const VALUE).read(), 41);
+
+            switch_to(&raw mut MAIN, THREAD);
+            assert_eq!((&raw const VALUE).read(), 42);
+        }
+    }
+
+    #[test]
+    fn test_fpu_state() {
+        let mut state = FpuState::new();
+        assert_eq!(&state.0[0..2], &[0x7F, 0x03]);
+        assert_eq!(&state.0[24..28], &[0x80, 0x1F, 0, 0]);
+
+        state.save();
+        state.restore();
+    }
+}
@@ -0,0 +1,91 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::arch::asm;
+
+#[derive(Clone, Copy)]
+pub struct Registers {
+    pub eax: u32,
+    pub ebx: u32,
+    pub ecx: u32,
+    pub edx: u32,
+}
+
+#[must_use]
+pub fn query(leaf: u32, subleaf: u32) -> Registers {
+    let eax: u32;
+    let ebx: u64;
+    let ecx: u32;
+    let edx: u32;
+    unsafe {
+        asm!(
+            "mov {0}, rbx",
+            "cpuid",
+            "xchg {0}, rbx",
+            out(reg) ebx,
+            inout("eax") leaf => eax,
+            inout("ecx") subleaf => ecx,
+            out("edx") edx,
+            options(nostack, preserves_flags),
+        );
+    }
+    Registers {
+        eax,
+        ebx: (ebx & 0xFFFF_FFFF) as u32,
+        ecx,
+        edx,
+    }
+}
+
+#[must_use]
+pub fn max_leaf() -> u32 {
+    query(0x0, 0).eax
+}
+
+#[must_use]
+pub fn max_extended_leaf() -> u32 {
+    query(0x8000_0000, 0).eax
+}
+
+#[must_use]
+pub fn has_always_running_timer() -> bool {
+    max_leaf() >= 0x6 && query(0x6, 0).eax & (1 << 2) != 0
+}
+
+#[must_use]
+pub fn has_apic() -> bool {
+    query(0x1, 0).edx & (1 << 9) != 0
+}
+
+#[must_use]
+pub fn has_invariant_tsc() -> bool {
+    max_extended_leaf() >= 0x8000_0007 && query(0x8000_0007, 0).edx & (1 << 8) != 0
+}
+
+#[must_use]
+pub fn has_rdtscp() -> bool {
+    max_extended_leaf() >= 0x8000_0001 && query(0x8000_0001, 0).edx & (1 << 27) != 0
+}
+
+#[must_use]
+pub fn has_tsc_deadline() -> bool {
+    query(0x1, 0).ecx & (1 << 24) != 0
+}
+
+#[must_use]
+pub fn has_x2apic() -> bool {
+    query(0x1, 0).ecx & (1 << 21) != 0
+}
@@ -0,0 +1,363 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::fmt;
+
+pub const MAX_LENGTH: usize = 15;
+
+const LEGACY_PREFIXES: [u8; 11] = [
+    0xF0, 0xF2, 0xF3, 0x2E, 0x36, 0x3E, 0x26, 0x64, 0x65, 0x66, 0x67,
+];
+
+const OPERAND_SIZE: u8 = 0x66;
+
+const ADDRESS_SIZE: u8 = 0x67;
+
+const ESCAPE: u8 = 0x0F;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Immediate {
+    None,
+    Byte,
+    Word,
+    Full,
+    Enter,
+    Relative,
+    Offset,
+    Wide,
+}
+
+impl Immediate {
+    fn size(self, operand16: bool, address32: bool, wide: bool) -> usize {
+        let full = if operand16 { 2 } else { 4 };
+
+        match self {
+            Self::None => 0,
+            Self::Byte => 1,
+            Self::Word => 2,
+            Self::Enter => 3,
+            Self::Relative => 4,
+            Self::Full => full,
+            Self::Offset => {
+                if address32 {
+                    4
+                } else {
+                    8
+                }
+            }
+            Self::Wide => {
+                if wide {
+                    8
+                } else {
+                    full
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Instruction<'a> {
+    bytes: &'a [u8],
+    prefixes: usize,
+    rex: Option<u8>,
+    opcode: usize,
+    modrm: Option<u8>,
+    length: usize,
+}
+
+impl Instruction<'_> {
+    #[must_use]
+    pub fn prefixes(&self) -> &[u8] {
+        &self.bytes[..self.prefixes]
+    }
+
+    #[must_use]
+    pub fn rex(&self) -> Option<u8> {
+        self.rex
+    }
+
+    #[must_use]
+    pub fn opcode(&self) -> &[u8] {
+        let start = self.prefixes + usize::from(self.rex.is_some());
+
+        &self.bytes[start..start + self.opcode]
+    }
+
+    #[must_use]
+    pub fn modrm(&self) -> Option<u8> {
+        self.modrm
+    }
+
+    #[must_use]
+    pub fn length(&self) -> usize {
+        self.length
+    }
+}
+
+impl fmt::Display for Instruction<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "prefixes")?;
+
+        if self.prefixes().is_empty() {
+            write!(formatter, " none")?;
+        }
+
+        for prefix in self.prefixes() {
+            write!(formatter, " {prefix:02x}")?;
+        }
+
+        match self.rex {
+            Some(rex) => write!(formatter, ", REX {rex:02x}")?,
+            None => write!(formatter, ", REX none")?,
+        }
+
+        write!(formatter, ", opcode")?;
+
+        for byte in self.opcode() {
+            write!(formatter, " {byte:02x}")?;
+        }
+
+        match self.modrm {
+            Some(modrm) => write!(formatter, ", ModRM {modrm:02x}")?,
+            None => write!(formatter, ", ModRM none")?,
+        }
+
+        write!(formatter, ", {} bytes", self.length)
+    }
+}
+
+fn primary(opcode: u8) -> Option<(bool, Immediate)> {
+    let modrm = match opcode {
+        0x00..=0x3F => opcode & 0x04 == 0,
+        0x63
+        | 0x69
+        | 0x6B
+        | 0x80..=0x8F
+        | 0xC0
+        | 0xC1
+        | 0xC6
+        | 0xC7
+        | 0xD0..=0xD3
+        | 0xD8..=0xDF
+        | 0xF6
+        | 0xF7
+        | 0xFE
+        | 0xFF => true,
+        0x62 | 0xC4 | 0xC5 => return None,
+        _ => false,
+    };
+
+    let immediate = match opcode {
+        0x00..=0x3F if opcode & 0x07 == 0x04 => Immediate::Byte,
+        0x00..=0x3F if opcode & 0x07 == 0x05 => Immediate::Full,
+        0x6A
+        | 0x6B
+        | 0x70..=0x7F
+        | 0x80
+        | 0x82
+        | 0x83
+        | 0xA8
+        | 0xB0..=0xB7
+        | 0xC0
+        | 0xC1
+        | 0xC6
+        | 0xCD
+        | 0xE0..=0xE7
+        | 0xEB => Immediate::Byte,
+        0x68 | 0x69 | 0x81 | 0xA9 | 0xC7 => Immediate::Full,
+        0xA0..=0xA3 => Immediate::Offset,
+        0xB8..=0xBF => Immediate::Wide,
+        0xC2 | 0xCA => Immediate::Word,
+        0xC8 => Immediate::Enter,
+        0xE8 | 0xE9 => Immediate::Relative,
+        _ => Immediate::None,
+    };
+
+    Some((modrm, immediate))
+}
+
+fn escape(opcode: u8) -> (bool, Immediate) {
+    let modrm = !matches!(
+        opcode,
+        0x05..=0x09 | 0x0B | 0x0E | 0x30..=0x37 | 0x77 | 0x80..=0x8F | 0xA0..=0xA2 | 0xA8..=0xAA
+            | 0xC8..=0xCF
+    );
+
+    let immediate = match opcode {
+        0x70..=0x73 | 0xA4 | 0xAC | 0xBA | 0xC2 | 0xC4..=0xC6 => Immediate::Byte,
+        0x80..=0x8F => Immediate::Relative,
+        _ => Immediate::None,
+    };
+
+    (modrm, immediate)
+}
+
+fn addressing(modrm: u8, sib: Option<u8>) -> Option<usize> {
+    let mode = modrm >> 6;
+    let rm = modrm & 0x07;
+
+    if mode == 0b11 {
+        return Some(0);
+    }
+
+    let base = if rm == 0b100 { sib? & 0x07 } else { rm };
+    let displacement = match mode {
+        0b00 if base == 0b101 => 4,
+        0b00 => 0,
+        0b01 => 1,
+        _ => 4,
+    };
+
+    Some(usize::from(rm == 0b100) + displacement)
+}
+
+#[must_use]
+pub fn decode(bytes: &[u8]) -> Option<Instruction<'_>> {
+    let bytes = &bytes[..bytes.len().min(MAX_LENGTH)];
+    let prefixes = bytes
+        .iter()
+        .take_while(|byte| LEGACY_PREFIXES.contains(byte))
+        .count();
+    let rex = bytes
+        .get(prefixes)
+        .copied()
+        .filter(|byte| byte & 0xF0 == 0x40);
+    let mut position = prefixes + usize::from(rex.is_some());
+
+    let first = *bytes.get(position)?;
+    let (opcode, has_modrm, mut immediate) = if first == ESCAPE {
+        match *bytes.get(position + 1)? {
+            0x38 => (3, true, Immediate::None),
+            0x3A => (3, true, Immediate::Byte),
+            second => {
+                let (has_modrm, immediate) = escape(second);
+                (2, has_modrm, immediate)
+            }
+        }
+    } else {
+        let (has_modrm, immediate) = primary(first)?;
+        (1, has_modrm, immediate)
+    };
+
+    position += opcode;
+
+    let modrm = if has_modrm {
+        let modrm = *bytes.get(position)?;
+        position += 1 + addressing(modrm, bytes.get(position + 1).copied())?;
+        Some(modrm)
+    } else {
+        None
+    };
+
+    if opcode == 1 && modrm.is_some_and(|modrm| (modrm >> 3) & 0x07 <= 1) {
+        immediate = match first {
+            0xF6 => Immediate::Byte,
+            0xF7 => Immediate::Full,
+            _ => immediate,
+        };
+    }
+
+    let operand16 = bytes[..prefixes].contains(&OPERAND_SIZE);
+    let address32 = bytes[..prefixes].contains(&ADDRESS_SIZE);
+    let wide = rex.is_some_and(|rex| rex & 0x08 != 0);
+    let length = position + immediate.size(operand16, address32, wide);
+
+    (length <= bytes.len()).then_some(Instruction {
+        bytes,
+        prefixes,
+        rex,
+        opcode,
+        modrm,
+        length,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn test_decode_plain() {
+        let instruction = decode(&[0x90, 0xCC]).unwrap();
+        assert!(instruction.prefixes().is_empty());
+        assert_eq!(instruction.rex(), None);
+        assert_eq!(instruction.opcode(), &[0x90]);
+        assert_eq!(instruction.modrm(), None);
+        assert_eq!(instruction.length(), 1);
+    }
+
+    #[test]
+    fn test_decode_rex_modrm() {
+        let instruction = decode(&[0x48, 0x89, 0x07, 0x00]).unwrap();
+        assert_eq!(instruction.rex(), Some(0x48));
+        assert_eq!(instruction.opcode(), &[0x89]);
+        assert_eq!(instruction.modrm(), Some(0x07));
+        assert_eq!(instruction.length(), 3);
+    }
+
+    #[test]
+    fn test_decode_prefixes() {
+        let instruction = decode(&[0xF0, 0x48, 0x0F, 0xB1, 0x0A]).unwrap();
+        assert_eq!(instruction.prefixes(), &[0xF0]);
+        assert_eq!(instruction.opcode(), &[0x0F, 0xB1]);
+        assert_eq!(instruction.length(), 5);
+    }
+
+    #[test]
+    fn test_decode_addressing() {
+        assert_eq!(decode(&[0x8B, 0x44, 0x24, 0x08]).unwrap().length(), 4);
+        assert_eq!(decode(&[0x8B, 0x05, 0, 0, 0, 0]).unwrap().length(), 6);
+        assert_eq!(decode(&[0x8B, 0x04, 0x25, 0, 0, 0, 0]).unwrap().length(), 7);
+        assert_eq!(decode(&[0x8B, 0x80, 0, 0, 0, 0]).unwrap().length(), 6);
+    }
+
+    #[test]
+    fn test_decode_immediates() {
+        assert_eq!(
+            decode(&[0x48, 0xB8, 0, 0, 0, 0, 0, 0, 0, 0])
+                .unwrap()
+                .length(),
+            10
+        );
+        assert_eq!(decode(&[0x66, 0x05, 0, 0]).unwrap().length(), 4);
+        assert_eq!(decode(&[0xF7, 0xC0, 0, 0, 0, 0]).unwrap().length(), 6);
+        assert_eq!(decode(&[0xF7, 0xE0]).unwrap().length(), 2);
+        assert_eq!(decode(&[0x0F, 0x84, 0, 0, 0, 0]).unwrap().length(), 6);
+        assert_eq!(decode(&[0x0F, 0x3A, 0x0F, 0xC1, 0x08]).unwrap().length(), 5);
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        assert_eq!(decode(&[0x0F, 0x0B]).unwrap().length(), 2);
+        assert!(decode(&[0xC5, 0xF8, 0x77]).is_none());
+        assert!(decode(&[0x48, 0x89]).is_none());
+        assert!(decode(&[0xE8, 0, 0]).is_none());
+        assert!(decode(&[]).is_none());
+    }
+
+    #[test]
+    fn test_instruction_display() {
+        let instruction = decode(&[0xF0, 0x48, 0x0F, 0xB1, 0x0A]).unwrap();
+        assert_eq!(
+            instruction.to_string(),
+            "prefixes f0, REX 48, opcode 0f b1, ModRM 0a, 5 bytes"
+        );
+    }
+}
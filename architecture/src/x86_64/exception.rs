@@ -0,0 +1,334 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::fmt;
+
+pub const EXCEPTIONS: u8 = 32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Exception {
+    DivisionError = 0,
+    Debug = 1,
+    NonMaskableInterrupt = 2,
+    Breakpoint = 3,
+    Overflow = 4,
+    BoundRangeExceeded = 5,
+    InvalidOpcode = 6,
+    DeviceNotAvailable = 7,
+    DoubleFault = 8,
+    CoprocessorSegmentOverrun = 9,
+    InvalidTss = 10,
+    SegmentNotPresent = 11,
+    StackSegmentFault = 12,
+    GeneralProtectionFault = 13,
+    PageFault = 14,
+    X87FloatingPoint = 16,
+    AlignmentCheck = 17,
+    MachineCheck = 18,
+    SimdFloatingPoint = 19,
+    Virtualization = 20,
+    ControlProtection = 21,
+    HypervisorInjection = 28,
+    VmmCommunication = 29,
+    Security = 30,
+}
+
+impl Exception {
+    #[must_use]
+    pub fn from_vector(vector: u8) -> Option<Self> {
+        match vector {
+            0 => Some(Self::DivisionError),
+            1 => Some(Self::Debug),
+            2 => Some(Self::NonMaskableInterrupt),
+            3 => Some(Self::Breakpoint),
+            4 => Some(Self::Overflow),
+            5 => Some(Self::BoundRangeExceeded),
+            6 => Some(Self::InvalidOpcode),
+            7 => Some(Self::DeviceNotAvailable),
+            8 => Some(Self::DoubleFault),
+            9 => Some(Self::CoprocessorSegmentOverrun),
+            10 => Some(Self::InvalidTss),
+            11 => Some(Self::SegmentNotPresent),
+            12 => Some(Self::StackSegmentFault),
+            13 => Some(Self::GeneralProtectionFault),
+            14 => Some(Self::PageFault),
+            16 => Some(Self::X87FloatingPoint),
+            17 => Some(Self::AlignmentCheck),
+            18 => Some(Self::MachineCheck),
+            19 => Some(Self::SimdFloatingPoint),
+            20 => Some(Self::Virtualization),
+            21 => Some(Self::ControlProtection),
+            28 => Some(Self::HypervisorInjection),
+            29 => Some(Self::VmmCommunication),
+            30 => Some(Self::Security),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn vector(self) -> u8 {
+        self as u8
+    }
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::DivisionError => "division error",
+            Self::Debug => "debug",
+            Self::NonMaskableInterrupt => "non-maskable interrupt",
+            Self::Breakpoint => "breakpoint",
+            Self::Overflow => "overflow",
+            Self::BoundRangeExceeded => "bound range exceeded",
+            Self::InvalidOpcode => "invalid opcode",
+            Self::DeviceNotAvailable => "device not available",
+            Self::DoubleFault => "double fault",
+            Self::CoprocessorSegmentOverrun => "coprocessor segment overrun",
+            Self::InvalidTss => "invalid TSS",
+            Self::SegmentNotPresent => "segment not present",
+            Self::StackSegmentFault => "stack segment fault",
+            Self::GeneralProtectionFault => "general protection fault",
+            Self::PageFault => "page fault",
+            Self::X87FloatingPoint => "x87 floating-point",
+            Self::AlignmentCheck => "alignment check",
+            Self::MachineCheck => "machine check",
+            Self::SimdFloatingPoint => "SIMD floating-point",
+            Self::Virtualization => "virtualization",
+            Self::ControlProtection => "control protection",
+            Self::HypervisorInjection => "hypervisor injection",
+            Self::VmmCommunication => "VMM communication",
+            Self::Security => "security",
+        }
+    }
+
+    #[must_use]
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Self::DivisionError => "#DE",
+            Self::Debug => "#DB",
+            Self::NonMaskableInterrupt => "NMI",
+            Self::Breakpoint => "#BP",
+            Self::Overflow => "#OF",
+            Self::BoundRangeExceeded => "#BR",
+            Self::InvalidOpcode => "#UD",
+            Self::DeviceNotAvailable => "#NM",
+            Self::DoubleFault => "#DF",
+            Self::CoprocessorSegmentOverrun => "CSO",
+            Self::InvalidTss => "#TS",
+            Self::SegmentNotPresent => "#NP",
+            Self::StackSegmentFault => "#SS",
+            Self::GeneralProtectionFault => "#GP",
+            Self::PageFault => "#PF",
+            Self::X87FloatingPoint => "#MF",
+            Self::AlignmentCheck => "#AC",
+            Self::MachineCheck => "#MC",
+            Self::SimdFloatingPoint => "#XM",
+            Self::Virtualization => "#VE",
+            Self::ControlProtection => "#CP",
+            Self::HypervisorInjection => "#HV",
+            Self::VmmCommunication => "#VC",
+            Self::Security => "#SX",
+        }
+    }
+
+    #[must_use]
+    pub fn has_selector_error(self) -> bool {
+        matches!(
+            self,
+            Self::InvalidTss
+                | Self::SegmentNotPresent
+                | Self::StackSegmentFault
+                | Self::GeneralProtectionFault
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DescriptorTable {
+    Gdt,
+    Idt,
+    Ldt,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SelectorError(u64);
+
+impl SelectorError {
+    #[must_use]
+    pub fn new(code: u64) -> Self {
+        Self(code)
+    }
+
+    #[must_use]
+    pub fn is_null(self) -> bool {
+        self.0 == 0
+    }
+
+    #[must_use]
+    pub fn is_external(self) -> bool {
+        self.0 & 0x1 != 0
+    }
+
+    #[must_use]
+    pub fn table(self) -> DescriptorTable {
+        if self.0 & 0x2 != 0 {
+            DescriptorTable::Idt
+        } else if self.0 & 0x4 != 0 {
+            DescriptorTable::Ldt
+        } else {
+            DescriptorTable::Gdt
+        }
+    }
+
+    #[must_use]
+    pub fn index(self) -> u16 {
+        ((self.0 >> 3) & 0x1FFF) as u16
+    }
+}
+
+impl fmt::Display for SelectorError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_null() {
+            return write!(formatter, "none");
+        }
+
+        let table = match self.table() {
+            DescriptorTable::Gdt => "GDT",
+            DescriptorTable::Idt => "IDT",
+            DescriptorTable::Ldt => "LDT",
+        };
+
+        write!(formatter, "{table} index {}", self.index())?;
+
+        if self.is_external() {
+            write!(formatter, " (external)")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PageFaultError(u64);
+
+impl PageFaultError {
+    pub const PRESENT: u64 = 1 << 0;
+    pub const WRITE: u64 = 1 << 1;
+    pub const USER: u64 = 1 << 2;
+    pub const RESERVED_WRITE: u64 = 1 << 3;
+    pub const INSTRUCTION_FETCH: u64 = 1 << 4;
+    pub const PROTECTION_KEY: u64 = 1 << 5;
+    pub const SHADOW_STACK: u64 = 1 << 6;
+    pub const SOFTWARE_GUARD: u64 = 1 << 15;
+
+    #[must_use]
+    pub fn new(code: u64) -> Self {
+        Self(code)
+    }
+
+    #[must_use]
+    pub fn contains(self, flag: u64) -> bool {
+        self.0 & flag == flag
+    }
+}
+
+impl fmt::Display for PageFaultError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let cause = if self.contains(Self::PRESENT) {
+            "protection violation"
+        } else {
+            "non-present page"
+        };
+        let access = if self.contains(Self::INSTRUCTION_FETCH) {
+            "instruction fetch"
+        } else if self.contains(Self::WRITE) {
+            "write"
+        } else {
+            "read"
+        };
+        let mode = if self.contains(Self::USER) {
+            "user"
+        } else {
+            "supervisor"
+        };
+
+        write!(formatter, "{cause} on {mode} {access}")?;
+
+        for (flag, name) in [
+            (Self::RESERVED_WRITE, "reserved bit set"),
+            (Self::PROTECTION_KEY, "protection key"),
+            (Self::SHADOW_STACK, "shadow stack"),
+            (Self::SOFTWARE_GUARD, "SGX"),
+        ] {
+            if self.contains(flag) {
+                write!(formatter, ", {name}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn test_from_vector() {
+        for vector in 0..EXCEPTIONS {
+            if let Some(exception) = Exception::from_vector(vector) {
+                assert_eq!(exception.vector(), vector);
+            }
+        }
+
+        assert_eq!(Exception::from_vector(15), None);
+        assert_eq!(Exception::from_vector(31), None);
+        assert_eq!(Exception::from_vector(32), None);
+    }
+
+    #[test]
+    fn test_selector_error() {
+        let error = SelectorError::new(0x2B);
+        assert!(error.is_external());
+        assert_eq!(error.table(), DescriptorTable::Idt);
+        assert_eq!(error.index(), 5);
+        assert_eq!(error.to_string(), "IDT index 5 (external)");
+
+        let error = SelectorError::new(0x1C);
+        assert!(!error.is_external());
+        assert_eq!(error.table(), DescriptorTable::Ldt);
+        assert_eq!(error.index(), 3);
+
+        assert_eq!(SelectorError::new(0x10).to_string(), "GDT index 2");
+        assert_eq!(SelectorError::new(0).to_string(), "none");
+    }
+
+    #[test]
+    fn test_page_fault_error() {
+        let error = PageFaultError::new(0x7);
+        assert!(error.contains(PageFaultError::PRESENT));
+        assert!(error.contains(PageFaultError::WRITE | PageFaultError::USER));
+        assert_eq!(error.to_string(), "protection violation on user write");
+
+        let error = PageFaultError::new(0x18);
+        assert_eq!(
+            error.to_string(),
+            "non-present page on supervisor instruction fetch, reserved bit set"
+        );
+    }
+}
@@ -0,0 +1,281 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::arch::asm;
+
+pub const USER_END: u64 = 0x0000_8000_0000_0000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    Fault,
+    InvalidAddress,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct Entry {
+    start: u64,
+    end: u64,
+    target: u64,
+}
+
+impl Entry {
+    #[must_use]
+    pub const fn new(start: u64, end: u64, target: u64) -> Self {
+        Self { start, end, target }
+    }
+
+    #[must_use]
+    pub fn contains(&self, address: u64) -> bool {
+        (self.start..self.end).contains(&address)
+    }
+
+    #[must_use]
+    pub fn target(&self) -> u64 {
+        self.target
+    }
+}
+
+#[must_use]
+pub fn search(entries: &[Entry], address: u64) -> Option<u64> {
+    entries
+        .iter()
+        .find(|entry| entry.contains(address))
+        .map(Entry::target)
+}
+
+fn is_user_range(address: u64, length: usize) -> bool {
+    address
+        .checked_add(length as u64)
+        .is_some_and(|end| end <= USER_END)
+}
+
+unsafe fn copy(destination: u64, source: u64, length: usize) -> Result<(), Error> {
+    let fault: u64;
+    unsafe {
+        asm!(
+            "xor {fault:e}, {fault:e}",
+            "2:",
+            "rep movsb",
+            "3:",
+            ".pushsection .exception_table, \"a\"",
+            ".balign 8",
+            ".quad 2b, 3b, 4f",
+            ".popsection",
+            "jmp 5f",
+            "4:",
+            "mov {fault:e}, 1",
+            "5:",
+            fault = out(reg) fault,
+            inout("rdi") destination => _,
+            inout("rsi") source => _,
+            inout("rcx") length => _,
+            options(nostack),
+        );
+    }
+
+    if fault == 0 {
+        Ok(())
+    } else {
+        Err(Error::Fault)
+    }
+}
+
+/// # Errors
+///
+/// Returns an error if the source range is not in user space or faults.
+pub fn copy_from_user(destination: &mut [u8], source: u64) -> Result<(), Error> {
+    if !is_user_range(source, destination.len()) {
+        return Err(Error::InvalidAddress);
+    }
+
+    unsafe { copy(destination.as_mut_ptr() as u64, source, destination.len()) }
+}
+
+/// # Errors
+///
+/// Returns an error if the destination range is not in user space or faults.
+pub fn copy_to_user(destination: u64, source: &[u8]) -> Result<(), Error> {
+    if !is_user_range(destination, source.len()) {
+        return Err(Error::InvalidAddress);
+    }
+
+    unsafe { copy(destination, source.as_ptr() as u64, source.len()) }
+}
+
+/// # Errors
+///
+/// Returns an error if reading the address faults.
+pub fn probe_read(address: u64) -> Result<u8, Error> {
+    let value: u8;
+    let fault: u64;
+    unsafe {
+        asm!(
+            "xor {fault:e}, {fault:e}",
+            "2:",
+            "mov {value}, byte ptr [{address}]",
+            "3:",
+            ".pushsection .exception_table, \"a\"",
+            ".balign 8",
+            ".quad 2b, 3b, 4f",
+            ".popsection",
+            "jmp 5f",
+            "4:",
+            "xor {value}, {value}",
+            "mov {fault:e}, 1",
+            "5:",
+            address = in(reg) address,
+            value = out(reg_byte) value,
+            fault = out(reg) fault,
+            options(nostack, readonly),
+        );
+    }
+
+    if fault == 0 {
+        Ok(value)
+    } else {
+        Err(Error::Fault)
+    }
+}
+
+/// # Errors
+///
+/// Returns an error if the MSR cannot be read.
+pub fn read_msr(index: u32) -> Result<u64, Error> {
+    let low: u32;
+    let high: u32;
+    let fault: u64;
+    unsafe {
+        asm!(
+            "xor {fault:e}, {fault:e}",
+            "2:",
+            "rdmsr",
+            "3:",
+            ".pushsection .exception_table, \"a\"",
+            ".balign 8",
+            ".quad 2b, 3b, 4f",
+            ".popsection",
+            "jmp 5f",
+            "4:",
+            "xor eax, eax",
+            "xor edx, edx",
+            "mov {fault:e}, 1",
+            "5:",
+            fault = out(reg) fault,
+            in("ecx") index,
+            out("eax") low,
+            out("edx") high,
+            options(nostack, nomem),
+        );
+    }
+
+    if fault == 0 {
+        Ok((u64::from(high) << 32) | u64::from(low))
+    } else {
+        Err(Error::Fault)
+    }
+}
+
+/// # Errors
+///
+/// Returns an error if the MSR cannot be written.
+pub fn write_msr(index: u32, value: u64) -> Result<(), Error> {
+    let low = (value & 0xFFFF_FFFF) as u32;
+    let high = (value >> 32) as u32;
+    let fault: u64;
+    unsafe {
+        asm!(
+            "xor {fault:e}, {fault:e}",
+            "2:",
+            "wrmsr",
+            "3:",
+            ".pushsection .exception_table, \"a\"",
+            ".balign 8",
+            ".quad 2b, 3b, 4f",
+            ".popsection",
+            "jmp 5f",
+            "4:",
+            "mov {fault:e}, 1",
+            "5:",
+            fault = out(reg) fault,
+            in("ecx") index,
+            in("eax") low,
+            in("edx") high,
+            options(nostack),
+        );
+    }
+
+    if fault == 0 {
+        Ok(())
+    } else {
+        Err(Error::Fault)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_contains() {
+        let entry = Entry::new(0x1000, 0x1004, 0x2000);
+        assert!(entry.contains(0x1000));
+        assert!(entry.contains(0x1003));
+        assert!(!entry.contains(0x1004));
+        assert!(!entry.contains(0x0FFF));
+    }
+
+    #[test]
+    fn test_search() {
+        let entries = [
+            Entry::new(0x1000, 0x1004, 0x2000),
+            Entry::new(0x3000, 0x3002, 0x4000),
+        ];
+        assert_eq!(search(&entries, 0x1002), Some(0x2000));
+        assert_eq!(search(&entries, 0x3001), Some(0x4000));
+        assert_eq!(search(&entries, 0x3002), None);
+        assert_eq!(search(&[], 0x1000), None);
+    }
+
+    #[test]
+    fn test_user_range() {
+        assert!(is_user_range(0x1000, 0x1000));
+        assert!(is_user_range(USER_END - 8, 8));
+        assert!(!is_user_range(USER_END - 8, 9));
+        assert!(!is_user_range(u64::MAX, 2));
+    }
+
+    #[test]
+    fn test_copy() {
+        let source = [1u8, 2, 3, 4];
+        let mut destination = [0u8; 4];
+        let result = unsafe {
+            copy(
+                destination.as_mut_ptr() as u64,
+                source.as_ptr() as u64,
+                source.len(),
+            )
+        };
+        assert_eq!(result, Ok(()));
+        assert_eq!(destination, source);
+    }
+
+    #[test]
+    fn test_probe_read() {
+        let value = 0x5Au8;
+        assert_eq!(probe_read(&raw const value as u64), Ok(0x5A));
+    }
+}
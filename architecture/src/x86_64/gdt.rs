@@ -14,13 +14,181 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use core::fmt::{self, Display, Formatter};
+
 use super::instruction;
 use super::register;
 use super::tss::Segment;
 
+pub const CAPACITY: usize = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    Full,
+    InvalidSelector,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Entry {
+    Null,
+    Code {
+        privilege: u8,
+    },
+    Data {
+        privilege: u8,
+    },
+    TaskState {
+        base: u64,
+        limit: u32,
+        busy: bool,
+    },
+    LocalTable {
+        base: u64,
+        limit: u32,
+    },
+    CallGate {
+        selector: Selector,
+        offset: u64,
+        privilege: u8,
+    },
+    Unknown(u64),
+}
+
+impl Entry {
+    /// # Panics
+    ///
+    /// Panics if the segment limit does not fit in 32 bits.
+    #[must_use]
+    pub fn task_state(segment: *const Segment) -> Self {
+        Self::TaskState {
+            base: segment as u64,
+            limit: u32::try_from(size_of::<Segment>() - 1).expect("Failed to calculate limit."),
+            busy: false,
+        }
+    }
+
+    #[must_use]
+    pub fn privilege_level(&self) -> u8 {
+        match *self {
+            Self::Code { privilege }
+            | Self::Data { privilege }
+            | Self::CallGate { privilege, .. } => privilege,
+            Self::Null | Self::TaskState { .. } | Self::LocalTable { .. } | Self::Unknown(_) => 0,
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Self::TaskState { .. } | Self::LocalTable { .. } | Self::CallGate { .. } => 2,
+            _ => 1,
+        }
+    }
+
+    fn encode(&self) -> [Descriptor; 2] {
+        match *self {
+            Self::Null => [Descriptor(0), Descriptor(0)],
+            Self::Code { privilege } => [
+                Descriptor::new(0x0, 0xFFFFF, 0x9A | (privilege << 5), 0xA),
+                Descriptor(0),
+            ],
+            Self::Data { privilege } => [
+                Descriptor::new(0x0, 0xFFFFF, 0x92 | (privilege << 5), 0xC),
+                Descriptor(0),
+            ],
+            Self::TaskState { base, limit, busy } => {
+                let access = if busy { 0x8B } else { 0x89 };
+                Descriptor::new_system(base, limit, access)
+            }
+            Self::LocalTable { base, limit } => Descriptor::new_system(base, limit, 0x82),
+            Self::CallGate {
+                selector,
+                offset,
+                privilege,
+            } => {
+                let access = u64::from(0x8C | (privilege << 5));
+                let low = (offset & 0xFFFF)
+                    | (u64::from(selector.0) << 16)
+                    | (access << 40)
+                    | (((offset >> 16) & 0xFFFF) << 48);
+
+                [Descriptor(low), Descriptor(offset >> 32)]
+            }
+            Self::Unknown(value) => [Descriptor(value), Descriptor(0)],
+        }
+    }
+
+    fn decode(low: Descriptor, high: Descriptor) -> Self {
+        let value = low.0;
+        if value == 0 {
+            return Self::Null;
+        }
+
+        let access = ((value >> 40) & 0xFF) as u8;
+        let privilege = (access >> 5) & 0x3;
+
+        if access & 0x10 != 0 {
+            return if access & 0x08 != 0 {
+                Self::Code { privilege }
+            } else {
+                Self::Data { privilege }
+            };
+        }
+
+        let base = ((value >> 16) & 0xFF_FFFF) | (((value >> 56) & 0xFF) << 24) | (high.0 << 32);
+        let limit = u32::try_from((value & 0xFFFF) | (((value >> 48) & 0xF) << 16))
+            .expect("Failed to decode limit.");
+
+        match access & 0x0F {
+            0x9 | 0xB => Self::TaskState {
+                base,
+                limit,
+                busy: access & 0x0F == 0xB,
+            },
+            0x2 => Self::LocalTable { base, limit },
+            0xC => Self::CallGate {
+                selector: Selector(((value >> 16) & 0xFFFF) as u16),
+                offset: (value & 0xFFFF) | (((value >> 48) & 0xFFFF) << 16) | (high.0 << 32),
+                privilege,
+            },
+            _ => Self::Unknown(value),
+        }
+    }
+}
+
+impl Display for Entry {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match *self {
+            Self::Null => write!(formatter, "null"),
+            Self::Code { privilege } => write!(formatter, "64-bit code (DPL {privilege})"),
+            Self::Data { privilege } => write!(formatter, "data (DPL {privilege})"),
+            Self::TaskState { base, limit, busy } => {
+                write!(formatter, "TSS at {base:#x} limit {limit:#x}")?;
+                if busy {
+                    write!(formatter, " (busy)")?;
+                }
+                Ok(())
+            }
+            Self::LocalTable { base, limit } => {
+                write!(formatter, "LDT at {base:#x} limit {limit:#x}")
+            }
+            Self::CallGate {
+                selector,
+                offset,
+                privilege,
+            } => write!(
+                formatter,
+                "call gate to {:#06x}:{offset:#x} (DPL {privilege})",
+                selector.0
+            ),
+            Self::Unknown(value) => write!(formatter, "unknown {value:#018x}"),
+        }
+    }
+}
+
 pub struct Table {
-    descriptors: [Descriptor; 7],
-    selectors: [Selector; 6],
+    descriptors: [Descriptor; CAPACITY],
+    occupied: u32,
+    heads: u32,
 }
 
 #[repr(C, packed(2))]
@@ -54,72 +222,165 @@ impl Descriptor {
         Self(value)
     }
 
-    fn new_system(base: u32) -> Self {
-        let value = u64::from(base);
+    fn new_system(base: u64, limit: u32, access: u8) -> [Self; 2] {
+        let base_low = (base & 0xFFFF_FFFF) as u32;
+        let base_high = base >> 32;
 
-        Self(value)
+        [Self::new(base_low, limit, access, 0x0), Self(base_high)]
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(transparent)]
 pub struct Selector(pub u16);
 
 impl Selector {
     #[must_use]
-    fn new(index: u16, privilege_level: u16) -> Self {
+    pub const fn new(index: u16, privilege_level: u16) -> Self {
         Self((index << 3) | privilege_level)
     }
+
+    #[must_use]
+    pub fn index(self) -> usize {
+        usize::from(self.0 >> 3)
+    }
+
+    #[must_use]
+    pub fn privilege_level(self) -> u8 {
+        (self.0 & 0x3) as u8
+    }
+}
+
+pub struct Builder {
+    table: Table,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Builder {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            table: Table::empty(),
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the table has no room for the entry.
+    #[must_use]
+    pub fn entry(mut self, entry: Entry) -> Self {
+        self.table
+            .add(entry)
+            .expect("Failed to add a descriptor to the table.");
+        self
+    }
+
+    #[must_use]
+    pub fn build(self) -> Table {
+        self.table
+    }
 }
 
 impl Table {
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self {
+            descriptors: [Descriptor(0); CAPACITY],
+            occupied: 1,
+            heads: 1,
+        }
+    }
+
     #[must_use]
     pub fn new(segment: *const Segment) -> Self {
-        let null_descriptor = Descriptor::new(0x0, 0x0, 0x0, 0x0);
-        let null_selector = Selector::new(0, 0);
+        Builder::new()
+            .entry(Entry::Code { privilege: 0 })
+            .entry(Entry::Data { privilege: 0 })
+            .entry(Entry::Code { privilege: 3 })
+            .entry(Entry::Data { privilege: 3 })
+            .entry(Entry::task_state(segment))
+            .build()
+    }
+
+    fn length(&self) -> usize {
+        (u32::BITS - self.occupied.leading_zeros()) as usize
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the table has no room for the entry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index does not fit in a selector.
+    pub fn add(&mut self, entry: Entry) -> Result<Selector, Error> {
+        let size = entry.size();
+        let mask = (1 << size) - 1;
+
+        let index = (1..=CAPACITY - size)
+            .find(|&index| self.occupied & (mask << index) == 0)
+            .ok_or(Error::Full)?;
+
+        let descriptors = entry.encode();
+        self.descriptors[index..index + size].copy_from_slice(&descriptors[..size]);
+        self.occupied |= mask << index;
+        self.heads |= 1 << index;
+
+        let index = u16::try_from(index).expect("Failed to convert index.");
+        Ok(Selector::new(index, u16::from(entry.privilege_level())))
+    }
 
-        let kernel_code_descriptor = Descriptor::new(0x0, 0xFFFFF, 0x9A, 0xA);
-        let kernel_code_selector = Selector::new(1, 0);
+    /// # Errors
+    ///
+    /// Returns an error if the selector does not name an entry.
+    pub fn remove(&mut self, selector: Selector) -> Result<Entry, Error> {
+        let index = selector.index();
+        if index == 0 || index >= CAPACITY || self.heads & (1 << index) == 0 {
+            return Err(Error::InvalidSelector);
+        }
 
-        let kernel_data_descriptor = Descriptor::new(0x0, 0xFFFFF, 0x92, 0xC);
-        let kernel_data_selector = Selector::new(2, 0);
+        let entry = self.decode(index);
+        let size = entry.size();
 
-        let user_code_descriptor = Descriptor::new(0x0, 0xFFFFF, 0xFA, 0xA);
-        let user_code_selector = Selector::new(3, 3);
+        for descriptor in &mut self.descriptors[index..index + size] {
+            *descriptor = Descriptor(0);
+        }
 
-        let user_data_descriptor = Descriptor::new(0x0, 0xFFFFF, 0xF2, 0xC);
-        let user_data_selector = Selector::new(4, 3);
+        self.occupied &= !(((1 << size) - 1) << index);
+        self.heads &= !(1 << index);
 
-        let task_state_base = segment as u64;
-        let task_state_base_low = (task_state_base & 0xFFFF_FFFF) as u32;
-        let task_state_base_high = (task_state_base >> 32) as u32;
-        let task_state_limit =
-            u32::try_from(size_of::<Segment>() - 1).expect("Failed to calculate limit.");
+        Ok(entry)
+    }
 
-        let task_state_descriptor_low =
-            Descriptor::new(task_state_base_low, task_state_limit, 0x89, 0x0);
-        let task_state_descriptor_high = Descriptor::new_system(task_state_base_high);
-        let task_state_selector = Selector::new(5, 0);
+    fn decode(&self, index: usize) -> Entry {
+        let high = self
+            .descriptors
+            .get(index + 1)
+            .copied()
+            .unwrap_or(Descriptor(0));
 
-        Self {
-            descriptors: [
-                null_descriptor,
-                kernel_code_descriptor,
-                kernel_data_descriptor,
-                user_code_descriptor,
-                user_data_descriptor,
-                task_state_descriptor_low,
-                task_state_descriptor_high,
-            ],
-            selectors: [
-                null_selector,
-                kernel_code_selector,
-                kernel_data_selector,
-                user_code_selector,
-                user_data_selector,
-                task_state_selector,
-            ],
+        Entry::decode(self.descriptors[index], high)
+    }
+
+    #[must_use]
+    pub fn entry(&self, selector: Selector) -> Option<Entry> {
+        let index = selector.index();
+        if index >= CAPACITY || self.heads & (1 << index) == 0 {
+            return None;
         }
+
+        Some(self.decode(index))
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (Selector, Entry)> + '_ {
+        (0..CAPACITY)
+            .filter(|&index| self.heads & (1 << index) != 0)
+            .map(|index| (self.selector(index), self.decode(index)))
     }
 
     fn base(&self) -> u64 {
@@ -132,7 +393,8 @@ impl Table {
     }
 
     fn limit(&self) -> u16 {
-        u16::try_from(size_of_val(&self.descriptors) - 1).expect("Failed to calculate limit.")
+        u16::try_from(self.length() * size_of::<Descriptor>() - 1)
+            .expect("Failed to calculate limit.")
     }
 
     #[must_use]
@@ -143,9 +405,15 @@ impl Table {
         }
     }
 
+    /// # Panics
+    ///
+    /// Panics if the index does not fit in a selector.
     #[must_use]
     pub fn selector(&self, index: usize) -> Selector {
-        self.selectors[index]
+        let privilege_level = self.decode(index).privilege_level();
+        let index = u16::try_from(index).expect("Failed to convert index.");
+
+        Selector::new(index, u16::from(privilege_level))
     }
 
     pub fn load(&self) {
@@ -169,7 +437,7 @@ mod tests {
 
     fn create_table() -> Table {
         let segment = Segment::new();
-        Table::new(&segment)
+        Table::new(&raw const segment)
     }
 
     fn get_descriptor(index: usize) -> u64 {
@@ -242,4 +510,117 @@ mod tests {
     fn test_task_state_selector() {
         assert_eq!(get_selector(5), 0x0028);
     }
+
+    #[test]
+    fn test_limit() {
+        let table = create_table();
+        assert_eq!(table.limit(), 7 * 8 - 1);
+    }
+
+    #[test]
+    fn test_decode_task_state() {
+        let table = Table::new(0xFFFF_8000_1234_5678 as *const Segment);
+
+        assert_eq!(
+            table.entry(Selector(0x28)),
+            Some(Entry::TaskState {
+                base: 0xFFFF_8000_1234_5678,
+                limit: 0x67,
+                busy: false
+            })
+        );
+    }
+
+    #[test]
+    fn test_call_gate() {
+        let mut table = create_table();
+        let gate = Entry::CallGate {
+            selector: Selector(0x0008),
+            offset: 0xFFFF_FFFF_8012_3456,
+            privilege: 3,
+        };
+
+        let selector = table.add(gate).unwrap();
+        assert_eq!(selector, Selector(0x003B));
+        assert_eq!(table.descriptor(7).0, 0x8012_EC00_0008_3456);
+        assert_eq!(table.descriptor(8).0, 0xFFFF_FFFF);
+        assert_eq!(table.entry(selector), Some(gate));
+    }
+
+    #[test]
+    fn test_local_table() {
+        let mut table = create_table();
+        let ldt = Entry::LocalTable {
+            base: 0xFFFF_8000_0000_1000,
+            limit: 0xFFF,
+        };
+
+        let selector = table.add(ldt).unwrap();
+        assert_eq!(table.entry(selector), Some(ldt));
+        assert_eq!(table.limit(), 9 * 8 - 1);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut table = create_table();
+
+        assert_eq!(
+            table.remove(Selector(0x001B)),
+            Ok(Entry::Code { privilege: 3 })
+        );
+        assert_eq!(table.remove(Selector(0x001B)), Err(Error::InvalidSelector));
+        assert_eq!(table.remove(Selector(0x0030)), Err(Error::InvalidSelector));
+        assert_eq!(table.remove(Selector(0x0000)), Err(Error::InvalidSelector));
+
+        let selector = table.add(Entry::Data { privilege: 0 }).unwrap();
+        assert_eq!(selector, Selector(0x0018));
+    }
+
+    #[test]
+    fn test_full() {
+        let mut builder = Builder::new();
+        for _ in 1..CAPACITY {
+            builder = builder.entry(Entry::Data { privilege: 0 });
+        }
+
+        let mut table = builder.build();
+        assert_eq!(table.add(Entry::Data { privilege: 0 }), Err(Error::Full));
+    }
+
+    #[test]
+    fn test_entries() {
+        let table = create_table();
+        let mut entries = table.entries();
+
+        assert_eq!(entries.next(), Some((Selector(0x0000), Entry::Null)));
+        assert_eq!(
+            entries.next(),
+            Some((Selector(0x0008), Entry::Code { privilege: 0 }))
+        );
+        assert_eq!(
+            entries.nth(3).map(|(selector, _)| selector),
+            Some(Selector(0x0028))
+        );
+        assert_eq!(entries.next(), None);
+    }
+
+    #[test]
+    fn test_display() {
+        extern crate std;
+        use std::string::ToString;
+
+        assert_eq!(
+            Entry::Code { privilege: 0 }.to_string(),
+            "64-bit code (DPL 0)"
+        );
+        assert_eq!(
+            Entry::CallGate {
+                selector: Selector(0x0008),
+                offset: 0x1000,
+                privilege: 3
+            }
+            .to_string(),
+            "call gate to 0x0008:0x1000 (DPL 3)"
+        );
+    }
 }
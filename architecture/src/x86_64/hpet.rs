@@ -0,0 +1,274 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::ptr;
+
+const CAPABILITIES: u64 = 0x000;
+const CONFIGURATION: u64 = 0x010;
+const INTERRUPT_STATUS: u64 = 0x020;
+const MAIN_COUNTER: u64 = 0x0F0;
+const TIMERS: u64 = 0x100;
+const TIMER_STRIDE: u64 = 0x20;
+
+const TIMER_CONFIGURATION: u64 = 0x00;
+const TIMER_COMPARATOR: u64 = 0x08;
+const TIMER_FSB_ROUTE: u64 = 0x10;
+
+const ENABLE: u64 = 1 << 0;
+const LEGACY_REPLACEMENT: u64 = 1 << 1;
+
+const COUNTER_64_BIT: u64 = 1 << 13;
+const LEGACY_REPLACEMENT_CAPABLE: u64 = 1 << 15;
+
+const LEVEL_TRIGGERED: u64 = 1 << 1;
+const INTERRUPT_ENABLE: u64 = 1 << 2;
+const PERIODIC: u64 = 1 << 3;
+const PERIODIC_CAPABLE: u64 = 1 << 4;
+const VALUE_SET: u64 = 1 << 6;
+const FORCE_32_BIT: u64 = 1 << 8;
+const ROUTE_SHIFT: u64 = 9;
+const ROUTE_MASK: u64 = 0x1F << ROUTE_SHIFT;
+const FSB_ENABLE: u64 = 1 << 14;
+const FSB_CAPABLE: u64 = 1 << 15;
+
+const FEMTOSECONDS: u64 = 1_000_000_000_000_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Route {
+    IoApic(u8),
+    Fsb { address: u32, data: u32 },
+}
+
+impl Route {
+    #[must_use]
+    pub fn message(destination: u8, vector: u8) -> Self {
+        Self::Fsb {
+            address: 0xFEE0_0000 | (u32::from(destination) << 12),
+            data: u32::from(vector),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimerMode {
+    OneShot,
+    Periodic,
+}
+
+fn configuration(current: u64, mode: TimerMode, route: Route) -> u64 {
+    let mut value = current
+        & !(LEVEL_TRIGGERED | PERIODIC | VALUE_SET | FORCE_32_BIT | ROUTE_MASK | FSB_ENABLE);
+
+    value |= INTERRUPT_ENABLE;
+
+    if mode == TimerMode::Periodic {
+        value |= PERIODIC | VALUE_SET;
+    }
+
+    match route {
+        Route::IoApic(interrupt) => value |= (u64::from(interrupt) << ROUTE_SHIFT) & ROUTE_MASK,
+        Route::Fsb { .. } => value |= FSB_ENABLE,
+    }
+
+    value
+}
+
+pub struct Hpet {
+    base: u64,
+}
+
+impl Hpet {
+    #[must_use]
+    pub const fn new(base: u64) -> Self {
+        Self { base }
+    }
+
+    fn read(&self, register: u64) -> u64 {
+        unsafe { ptr::read_volatile((self.base + register) as *const u64) }
+    }
+
+    fn write(&self, register: u64, value: u64) {
+        unsafe {
+            ptr::write_volatile((self.base + register) as *mut u64, value);
+        }
+    }
+
+    #[must_use]
+    pub fn revision(&self) -> u8 {
+        (self.read(CAPABILITIES) & 0xFF) as u8
+    }
+
+    #[must_use]
+    pub fn timer_count(&self) -> u8 {
+        ((self.read(CAPABILITIES) >> 8) & 0x1F) as u8 + 1
+    }
+
+    #[must_use]
+    pub fn is_64_bit(&self) -> bool {
+        self.read(CAPABILITIES) & COUNTER_64_BIT != 0
+    }
+
+    #[must_use]
+    pub fn has_legacy_replacement(&self) -> bool {
+        self.read(CAPABILITIES) & LEGACY_REPLACEMENT_CAPABLE != 0
+    }
+
+    #[must_use]
+    pub fn period(&self) -> u32 {
+        (self.read(CAPABILITIES) >> 32) as u32
+    }
+
+    #[must_use]
+    pub fn frequency(&self) -> u64 {
+        FEMTOSECONDS / u64::from(self.period().max(1))
+    }
+
+    pub fn enable(&self) {
+        let configuration = self.read(CONFIGURATION);
+        self.write(CONFIGURATION, configuration | ENABLE);
+    }
+
+    pub fn disable(&self) {
+        let configuration = self.read(CONFIGURATION);
+        self.write(CONFIGURATION, configuration & !ENABLE);
+    }
+
+    pub fn set_legacy_replacement(&self, enabled: bool) {
+        let configuration = self.read(CONFIGURATION);
+
+        if enabled {
+            self.write(CONFIGURATION, configuration | LEGACY_REPLACEMENT);
+        } else {
+            self.write(CONFIGURATION, configuration & !LEGACY_REPLACEMENT);
+        }
+    }
+
+    #[must_use]
+    pub fn counter(&self) -> u64 {
+        self.read(MAIN_COUNTER)
+    }
+
+    pub fn set_counter(&self, value: u64) {
+        self.write(MAIN_COUNTER, value);
+    }
+
+    #[must_use]
+    pub fn interrupt_status(&self) -> u32 {
+        (self.read(INTERRUPT_STATUS) & 0xFFFF_FFFF) as u32
+    }
+
+    pub fn clear_interrupt(&self, timer: u8) {
+        self.write(INTERRUPT_STATUS, 1 << timer);
+    }
+
+    fn timer_register(timer: u8, register: u64) -> u64 {
+        TIMERS + u64::from(timer) * TIMER_STRIDE + register
+    }
+
+    #[must_use]
+    pub fn routes(&self, timer: u8) -> u32 {
+        (self.read(Self::timer_register(timer, TIMER_CONFIGURATION)) >> 32) as u32
+    }
+
+    #[must_use]
+    pub fn is_periodic_capable(&self, timer: u8) -> bool {
+        self.read(Self::timer_register(timer, TIMER_CONFIGURATION)) & PERIODIC_CAPABLE != 0
+    }
+
+    #[must_use]
+    pub fn is_fsb_capable(&self, timer: u8) -> bool {
+        self.read(Self::timer_register(timer, TIMER_CONFIGURATION)) & FSB_CAPABLE != 0
+    }
+
+    fn configure(&self, timer: u8, mode: TimerMode, route: Route) {
+        let register = Self::timer_register(timer, TIMER_CONFIGURATION);
+
+        if let Route::Fsb { address, data } = route {
+            let value = (u64::from(address) << 32) | u64::from(data);
+            self.write(Self::timer_register(timer, TIMER_FSB_ROUTE), value);
+        }
+
+        self.write(register, configuration(self.read(register), mode, route));
+    }
+
+    pub fn set_periodic(&self, timer: u8, period: u64, route: Route) {
+        self.configure(timer, TimerMode::Periodic, route);
+
+        let comparator = Self::timer_register(timer, TIMER_COMPARATOR);
+        self.write(comparator, self.counter() + period);
+        self.write(comparator, period);
+    }
+
+    pub fn set_one_shot(&self, timer: u8, deadline: u64, route: Route) {
+        self.configure(timer, TimerMode::OneShot, route);
+
+        self.write(Self::timer_register(timer, TIMER_COMPARATOR), deadline);
+    }
+
+    pub fn stop(&self, timer: u8) {
+        let register = Self::timer_register(timer, TIMER_CONFIGURATION);
+        self.write(
+            register,
+            self.read(register) & !(INTERRUPT_ENABLE | PERIODIC),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_configuration_io_apic() {
+        let value = configuration(PERIODIC_CAPABLE, TimerMode::OneShot, Route::IoApic(2));
+        assert_eq!(value, PERIODIC_CAPABLE | INTERRUPT_ENABLE | (2 << 9));
+
+        let value = configuration(0, TimerMode::Periodic, Route::IoApic(20));
+        assert_eq!(value, INTERRUPT_ENABLE | PERIODIC | VALUE_SET | (20 << 9));
+    }
+
+    #[test]
+    fn test_configuration_fsb() {
+        let current = LEVEL_TRIGGERED | (5 << 9) | FSB_CAPABLE;
+        let value = configuration(
+            current,
+            TimerMode::OneShot,
+            Route::Fsb {
+                address: 0xFEE0_0000,
+                data: 0x40,
+            },
+        );
+        assert_eq!(value, FSB_CAPABLE | FSB_ENABLE | INTERRUPT_ENABLE);
+    }
+
+    #[test]
+    fn test_route_message() {
+        assert_eq!(
+            Route::message(3, 0x41),
+            Route::Fsb {
+                address: 0xFEE0_3000,
+                data: 0x41,
+            }
+        );
+    }
+
+    #[test]
+    fn test_timer_register() {
+        assert_eq!(Hpet::timer_register(0, TIMER_CONFIGURATION), 0x100);
+        assert_eq!(Hpet::timer_register(2, TIMER_COMPARATOR), 0x148);
+        assert_eq!(Hpet::timer_register(1, TIMER_FSB_ROUTE), 0x130);
+    }
+}
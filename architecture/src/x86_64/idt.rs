@@ -16,18 +16,78 @@
 
 use super::gdt::Selector;
 use super::instruction;
+use super::trap::TrapFrame;
 
-type Handler = extern "x86-interrupt" fn(Frame);
-type HaltHandler = extern "x86-interrupt" fn(Frame) -> !;
-type ErrorHandler = extern "x86-interrupt" fn(Frame, u64);
-type HaltErrorHandler = extern "x86-interrupt" fn(Frame, u64) -> !;
+pub const SHARED_HANDLERS: usize = 4;
+pub const VECTORS: usize = 256;
 
+pub type Action = fn(&mut TrapFrame) -> Status;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Gate {
     Null = 0xE,
     Interrupt = 0x8E,
     Trap = 0x8F,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Handled,
+    Unhandled,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    Full,
+    NotRegistered,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Options {
+    gate: Gate,
+    stack_index: u8,
+    privilege_level: u8,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Options {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            gate: Gate::Interrupt,
+            stack_index: 0,
+            privilege_level: 0,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_gate(mut self, gate: Gate) -> Self {
+        self.gate = gate;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_stack_index(mut self, stack_index: u8) -> Self {
+        self.stack_index = stack_index & 0x7;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_privilege_level(mut self, privilege_level: u8) -> Self {
+        self.privilege_level = privilege_level & 0x3;
+        self
+    }
+
+    fn type_attributes(self) -> u8 {
+        (self.gate as u8) | (self.privilege_level << 5)
+    }
+}
+
 #[repr(C, packed(2))]
 pub struct Register {
     limit: u16,
@@ -61,286 +121,137 @@ impl Default for Descriptor {
 }
 
 impl Descriptor {
-    pub fn new(
-        handler: Handler,
-        selector: Selector,
-        interrupt_stack_table: u8,
-        gate: Gate,
-    ) -> Self {
-        let address = handler as usize as u64;
-
+    #[must_use]
+    pub fn with_address(address: u64, selector: Selector, options: Options) -> Self {
         Self {
             offset_low: (address & 0xFFFF) as u16,
             selector,
-            interrupt_stack_table,
-            type_attributes: gate as u8,
+            interrupt_stack_table: options.stack_index,
+            type_attributes: options.type_attributes(),
             offset_middle: ((address >> 16) & 0xFFFF) as u16,
             offset_high: (address >> 32) as u32,
             reserved: 0,
         }
     }
 
-    pub fn new_with_halt(
-        handler: HaltHandler,
-        selector: Selector,
-        interrupt_stack_table: u8,
-        gate: Gate,
-    ) -> Self {
-        let address = handler as usize as u64;
-
-        Self {
-            offset_low: (address & 0xFFFF) as u16,
-            selector,
-            interrupt_stack_table,
-            type_attributes: gate as u8,
-            offset_middle: ((address >> 16) & 0xFFFF) as u16,
-            offset_high: (address >> 32) as u32,
-            reserved: 0,
-        }
+    #[must_use]
+    pub fn address(&self) -> u64 {
+        u64::from(self.offset_low)
+            | (u64::from(self.offset_middle) << 16)
+            | (u64::from(self.offset_high) << 32)
     }
 
-    pub fn new_with_error(
-        handler: ErrorHandler,
-        selector: Selector,
-        interrupt_stack_table: u8,
-        gate: Gate,
-    ) -> Self {
-        let address = handler as usize as u64;
-
-        Self {
-            offset_low: (address & 0xFFFF) as u16,
-            selector,
-            interrupt_stack_table,
-            type_attributes: gate as u8,
-            offset_middle: ((address >> 16) & 0xFFFF) as u16,
-            offset_high: (address >> 32) as u32,
-            reserved: 0,
-        }
+    #[must_use]
+    pub fn is_present(&self) -> bool {
+        self.type_attributes & 0x80 != 0
     }
+}
 
-    pub fn new_with_halt_error(
-        handler: HaltErrorHandler,
-        selector: Selector,
-        interrupt_stack_table: u8,
-        gate: Gate,
-    ) -> Self {
-        let address = handler as usize as u64;
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Handle {
+    vector: u8,
+    index: usize,
+}
 
-        Self {
-            offset_low: (address & 0xFFFF) as u16,
-            selector,
-            interrupt_stack_table,
-            type_attributes: gate as u8,
-            offset_middle: ((address >> 16) & 0xFFFF) as u16,
-            offset_high: (address >> 32) as u32,
-            reserved: 0,
-        }
+impl Handle {
+    #[must_use]
+    pub fn vector(&self) -> u8 {
+        self.vector
     }
 }
 
-#[repr(C)]
-pub struct Frame {
-    instruction_pointer: u64,
-    code_segment: u64,
-    cpu_flags: u64,
-    stack_pointer: u64,
-    stack_segment: u64,
-}
+pub type Chain = [Option<Action>; SHARED_HANDLERS];
 
-pub struct Handlers {
-    pub division_error_handler: Handler,
-    pub debug_handler: Handler,
-    pub non_maskable_interrupt_handler: Handler,
-    pub breakpoint_handler: Handler,
-    pub overflow_handler: Handler,
-    pub bound_range_exceeded_handler: Handler,
-    pub invalid_opcode_handler: Handler,
-    pub device_not_available_handler: Handler,
-    pub double_fault_handler: HaltErrorHandler,
-    pub invalid_tss_handler: ErrorHandler,
-    pub segment_not_present_handler: ErrorHandler,
-    pub stack_segment_fault_handler: ErrorHandler,
-    pub general_protection_fault_handler: ErrorHandler,
-    pub page_fault_handler: ErrorHandler,
-    pub x87_floating_point_handler: Handler,
-    pub alignment_check_handler: ErrorHandler,
-    pub machine_check_handler: HaltHandler,
-    pub simd_floating_point_handler: Handler,
-    pub virtualization_handler: Handler,
-    pub control_protection_handler: ErrorHandler,
-    pub hypervisor_injection_handler: Handler,
-    pub vmm_communication_handler: ErrorHandler,
-    pub security_handler: ErrorHandler,
+pub struct Registry {
+    chains: [Chain; VECTORS],
 }
 
-pub struct Table {
-    descriptors: [Descriptor; 256],
+impl Default for Registry {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl Table {
+impl Registry {
     #[must_use]
-    pub fn new(handlers: &Handlers, selector: Selector) -> Self {
-        let null_descriptor = Descriptor::default();
-        let mut descriptors = [null_descriptor; 256];
-
-        let division_error_descriptor = Descriptor::new(
-            handlers.division_error_handler,
-            selector,
-            0,
-            Gate::Interrupt,
-        );
-        descriptors[0] = division_error_descriptor;
-
-        let debug_descriptor = Descriptor::new(handlers.debug_handler, selector, 0, Gate::Trap);
-        descriptors[1] = debug_descriptor;
-
-        let non_maskable_interrupt_descriptor = Descriptor::new(
-            handlers.non_maskable_interrupt_handler,
-            selector,
-            0,
-            Gate::Interrupt,
-        );
-        descriptors[2] = non_maskable_interrupt_descriptor;
-
-        let breakpoint_descriptor =
-            Descriptor::new(handlers.breakpoint_handler, selector, 0, Gate::Trap);
-        descriptors[3] = breakpoint_descriptor;
-
-        let overflow_descriptor =
-            Descriptor::new(handlers.overflow_handler, selector, 0, Gate::Trap);
-        descriptors[4] = overflow_descriptor;
-
-        let bound_range_exceeded_descriptor = Descriptor::new(
-            handlers.bound_range_exceeded_handler,
-            selector,
-            0,
-            Gate::Interrupt,
-        );
-        descriptors[5] = bound_range_exceeded_descriptor;
-
-        let invalid_opcode_descriptor = Descriptor::new(
-            handlers.invalid_opcode_handler,
-            selector,
-            0,
-            Gate::Interrupt,
-        );
-        descriptors[6] = invalid_opcode_descriptor;
-
-        let device_not_available_descriptor = Descriptor::new(
-            handlers.device_not_available_handler,
-            selector,
-            0,
-            Gate::Interrupt,
-        );
-        descriptors[7] = device_not_available_descriptor;
+    pub const fn new() -> Self {
+        Self {
+            chains: [[None; SHARED_HANDLERS]; VECTORS],
+        }
+    }
 
-        let double_fault_descriptor = Descriptor::new_with_halt_error(
-            handlers.double_fault_handler,
-            selector,
-            0,
-            Gate::Interrupt,
-        );
-        descriptors[8] = double_fault_descriptor;
+    /// # Errors
+    ///
+    /// Returns an error if the vector has no free handler slot.
+    pub fn register(&mut self, vector: u8, action: Action) -> Result<Handle, Error> {
+        let chain = &mut self.chains[usize::from(vector)];
+        let index = chain.iter().position(Option::is_none).ok_or(Error::Full)?;
 
-        let invalid_tss_descriptor =
-            Descriptor::new_with_error(handlers.invalid_tss_handler, selector, 0, Gate::Interrupt);
-        descriptors[10] = invalid_tss_descriptor;
+        chain[index] = Some(action);
 
-        let segment_not_present_descriptor = Descriptor::new_with_error(
-            handlers.segment_not_present_handler,
-            selector,
-            0,
-            Gate::Interrupt,
-        );
-        descriptors[11] = segment_not_present_descriptor;
+        Ok(Handle { vector, index })
+    }
 
-        let stack_segment_fault_descriptor = Descriptor::new_with_error(
-            handlers.stack_segment_fault_handler,
-            selector,
-            0,
-            Gate::Interrupt,
-        );
-        descriptors[12] = stack_segment_fault_descriptor;
+    /// # Errors
+    ///
+    /// Returns an error if the handle is not registered.
+    pub fn unregister(&mut self, handle: Handle) -> Result<(), Error> {
+        let chain = &mut self.chains[usize::from(handle.vector)];
 
-        let general_protection_fault_descriptor = Descriptor::new_with_error(
-            handlers.general_protection_fault_handler,
-            selector,
-            0,
-            Gate::Interrupt,
-        );
-        descriptors[13] = general_protection_fault_descriptor;
+        chain[handle.index].take().ok_or(Error::NotRegistered)?;
 
-        let page_fault_descriptor =
-            Descriptor::new_with_error(handlers.page_fault_handler, selector, 0, Gate::Interrupt);
-        descriptors[14] = page_fault_descriptor;
+        Ok(())
+    }
 
-        let x87_floating_point_descriptor = Descriptor::new(
-            handlers.x87_floating_point_handler,
-            selector,
-            0,
-            Gate::Interrupt,
-        );
-        descriptors[16] = x87_floating_point_descriptor;
+    /// # Errors
+    ///
+    /// Returns an error if the handle is not registered.
+    pub fn replace(&mut self, handle: Handle, action: Action) -> Result<(), Error> {
+        let slot = &mut self.chains[usize::from(handle.vector)][handle.index];
 
-        let alignment_check_descriptor = Descriptor::new_with_error(
-            handlers.alignment_check_handler,
-            selector,
-            0,
-            Gate::Interrupt,
-        );
-        descriptors[17] = alignment_check_descriptor;
+        let registered = slot.as_mut().ok_or(Error::NotRegistered)?;
+        *registered = action;
 
-        let machine_check_descriptor =
-            Descriptor::new_with_halt(handlers.machine_check_handler, selector, 0, Gate::Interrupt);
-        descriptors[18] = machine_check_descriptor;
+        Ok(())
+    }
 
-        let simd_floating_point_descriptor = Descriptor::new(
-            handlers.simd_floating_point_handler,
-            selector,
-            0,
-            Gate::Interrupt,
-        );
-        descriptors[19] = simd_floating_point_descriptor;
+    #[must_use]
+    pub fn is_registered(&self, vector: u8) -> bool {
+        self.chains[usize::from(vector)].iter().any(Option::is_some)
+    }
 
-        let virtualization_descriptor = Descriptor::new(
-            handlers.virtualization_handler,
-            selector,
-            0,
-            Gate::Interrupt,
-        );
-        descriptors[20] = virtualization_descriptor;
+    #[must_use]
+    pub fn chain(&self, vector: u8) -> Chain {
+        self.chains[usize::from(vector)]
+    }
 
-        let control_protection_descriptor = Descriptor::new_with_error(
-            handlers.control_protection_handler,
-            selector,
-            0,
-            Gate::Interrupt,
-        );
-        descriptors[21] = control_protection_descriptor;
+    #[must_use]
+    pub fn dispatch(chain: &Chain, frame: &mut TrapFrame) -> Status {
+        let mut status = Status::Unhandled;
 
-        let hypervisor_injection_descriptor = Descriptor::new(
-            handlers.hypervisor_injection_handler,
-            selector,
-            0,
-            Gate::Interrupt,
-        );
-        descriptors[28] = hypervisor_injection_descriptor;
+        for action in chain.iter().flatten() {
+            if action(frame) == Status::Handled {
+                status = Status::Handled;
+            }
+        }
 
-        let vmm_communication_descriptor = Descriptor::new_with_error(
-            handlers.vmm_communication_handler,
-            selector,
-            0,
-            Gate::Interrupt,
-        );
-        descriptors[29] = vmm_communication_descriptor;
+        status
+    }
+}
 
-        let security_descriptor =
-            Descriptor::new_with_error(handlers.security_handler, selector, 0, Gate::Interrupt);
-        descriptors[30] = security_descriptor;
+pub struct Table {
+    descriptors: [Descriptor; VECTORS],
+}
 
-        Self { descriptors }
+impl Default for Table {
+    fn default() -> Self {
+        Self {
+            descriptors: [Descriptor::default(); VECTORS],
+        }
     }
+}
 
+impl Table {
     fn base(&self) -> u64 {
         self.descriptors.as_ptr() as u64
     }
@@ -349,6 +260,19 @@ impl Table {
         u16::try_from(size_of_val(&self.descriptors) - 1).expect("Failed to calculate limit.")
     }
 
+    #[must_use]
+    pub fn descriptor(&self, vector: u8) -> Descriptor {
+        self.descriptors[usize::from(vector)]
+    }
+
+    pub fn set(&mut self, vector: u8, descriptor: Descriptor) {
+        self.descriptors[usize::from(vector)] = descriptor;
+    }
+
+    pub fn remove(&mut self, vector: u8) {
+        self.descriptors[usize::from(vector)] = Descriptor::default();
+    }
+
     pub fn load(&self) {
         let register = self.register();
         instruction::lidt(&register);
@@ -363,3 +287,100 @@ impl Table {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handled(frame: &mut TrapFrame) -> Status {
+        frame.rax += 1;
+        Status::Handled
+    }
+
+    fn unhandled(_frame: &mut TrapFrame) -> Status {
+        Status::Unhandled
+    }
+
+    #[test]
+    fn test_options_type_attributes() {
+        assert_eq!(Options::new().type_attributes(), 0x8E);
+        assert_eq!(Options::new().with_gate(Gate::Trap).type_attributes(), 0x8F);
+        assert_eq!(
+            Options::new()
+                .with_gate(Gate::Trap)
+                .with_privilege_level(3)
+                .type_attributes(),
+            0xEF
+        );
+    }
+
+    #[test]
+    fn test_descriptor_address() {
+        let options = Options::new().with_stack_index(2);
+        let descriptor = Descriptor::with_address(0xFFFF_FFFF_8012_3456, Selector(0x08), options);
+        assert_eq!(descriptor.address(), 0xFFFF_FFFF_8012_3456);
+        assert_eq!(descriptor.interrupt_stack_table, 2);
+        assert!(descriptor.is_present());
+        assert!(!Descriptor::default().is_present());
+    }
+
+    #[test]
+    fn test_registry_register() {
+        let mut registry = Registry::new();
+        assert!(!registry.is_registered(0x40));
+
+        let handle = registry.register(0x40, handled).unwrap();
+        assert_eq!(handle.vector(), 0x40);
+        assert!(registry.is_registered(0x40));
+
+        registry.unregister(handle).unwrap();
+        assert!(!registry.is_registered(0x40));
+        assert_eq!(registry.unregister(handle), Err(Error::NotRegistered));
+    }
+
+    #[test]
+    fn test_registry_replace() {
+        let mut registry = Registry::new();
+        let mut frame = TrapFrame::default();
+
+        let handle = registry.register(0x43, unhandled).unwrap();
+        registry.replace(handle, handled).unwrap();
+
+        let chain = registry.chain(0x43);
+        assert_eq!(Registry::dispatch(&chain, &mut frame), Status::Handled);
+
+        registry.unregister(handle).unwrap();
+        assert_eq!(registry.replace(handle, handled), Err(Error::NotRegistered));
+        assert!(!registry.is_registered(0x43));
+    }
+
+    #[test]
+    fn test_registry_full() {
+        let mut registry = Registry::new();
+
+        for _ in 0..SHARED_HANDLERS {
+            registry.register(0x41, unhandled).unwrap();
+        }
+
+        assert_eq!(registry.register(0x41, handled), Err(Error::Full));
+    }
+
+    #[test]
+    fn test_registry_dispatch() {
+        let mut registry = Registry::new();
+        let mut frame = TrapFrame::default();
+
+        let chain = registry.chain(0x42);
+        assert_eq!(Registry::dispatch(&chain, &mut frame), Status::Unhandled);
+
+        registry.register(0x42, unhandled).unwrap();
+        let chain = registry.chain(0x42);
+        assert_eq!(Registry::dispatch(&chain, &mut frame), Status::Unhandled);
+
+        registry.register(0x42, handled).unwrap();
+        registry.register(0x42, handled).unwrap();
+        let chain = registry.chain(0x42);
+        assert_eq!(Registry::dispatch(&chain, &mut frame), Status::Handled);
+        assert_eq!(frame.rax, 2);
+    }
+}
@@ -39,6 +39,57 @@ pub fn inb(port: u16) -> u8 {
     value
 }
 
+#[must_use]
+pub fn inl(port: u16) -> u32 {
+    let value: u32;
+    unsafe {
+        asm!("in eax, dx", out("eax") value, in("dx") port);
+    }
+    value
+}
+
+pub fn insb(port: u16, buffer: &mut [u8]) {
+    unsafe {
+        asm!(
+            "rep insb",
+            in("dx") port,
+            inout("rdi") buffer.as_mut_ptr() => _,
+            inout("rcx") buffer.len() => _,
+        );
+    }
+}
+
+pub fn insl(port: u16, buffer: &mut [u32]) {
+    unsafe {
+        asm!(
+            "rep insd",
+            in("dx") port,
+            inout("rdi") buffer.as_mut_ptr() => _,
+            inout("rcx") buffer.len() => _,
+        );
+    }
+}
+
+pub fn insw(port: u16, buffer: &mut [u16]) {
+    unsafe {
+        asm!(
+            "rep insw",
+            in("dx") port,
+            inout("rdi") buffer.as_mut_ptr() => _,
+            inout("rcx") buffer.len() => _,
+        );
+    }
+}
+
+#[must_use]
+pub fn inw(port: u16) -> u16 {
+    let value: u16;
+    unsafe {
+        asm!("in ax, dx", out("ax") value, in("dx") port);
+    }
+    value
+}
+
 pub fn int<const VECTOR: u8>() {
     unsafe {
         asm!("int {vector}", vector = const VECTOR);
@@ -51,6 +102,12 @@ pub fn int3() {
     }
 }
 
+pub fn invlpg(address: u64) {
+    unsafe {
+        asm!("invlpg [{}]", in(reg) address, options(nostack, preserves_flags));
+    }
+}
+
 pub fn lgdt(register: &gdt::Register) {
     unsafe {
         asm!("lgdt [{}]", in(reg) register);
@@ -75,8 +132,104 @@ pub fn outb(port: u16, value: u8) {
     }
 }
 
+pub fn outl(port: u16, value: u32) {
+    unsafe {
+        asm!("out dx, eax", in("dx") port, in("eax") value);
+    }
+}
+
+pub fn outsb(port: u16, buffer: &[u8]) {
+    unsafe {
+        asm!(
+            "rep outsb",
+            in("dx") port,
+            inout("rsi") buffer.as_ptr() => _,
+            inout("rcx") buffer.len() => _,
+        );
+    }
+}
+
+pub fn outsl(port: u16, buffer: &[u32]) {
+    unsafe {
+        asm!(
+            "rep outsd",
+            in("dx") port,
+            inout("rsi") buffer.as_ptr() => _,
+            inout("rcx") buffer.len() => _,
+        );
+    }
+}
+
+pub fn outsw(port: u16, buffer: &[u16]) {
+    unsafe {
+        asm!(
+            "rep outsw",
+            in("dx") port,
+            inout("rsi") buffer.as_ptr() => _,
+            inout("rcx") buffer.len() => _,
+        );
+    }
+}
+
+pub fn outw(port: u16, value: u16) {
+    unsafe {
+        asm!("out dx, ax", in("dx") port, in("ax") value);
+    }
+}
+
+pub fn pause() {
+    unsafe {
+        asm!("pause");
+    }
+}
+
+#[must_use]
+pub fn rdmsr(index: u32) -> u64 {
+    let low: u32;
+    let high: u32;
+    unsafe {
+        asm!("rdmsr", in("ecx") index, out("eax") low, out("edx") high);
+    }
+    (u64::from(high) << 32) | u64::from(low)
+}
+
+#[must_use]
+pub fn rdtsc() -> u64 {
+    let low: u32;
+    let high: u32;
+    unsafe {
+        asm!("rdtsc", out("eax") low, out("edx") high);
+    }
+    (u64::from(high) << 32) | u64::from(low)
+}
+
+#[must_use]
+pub fn rdtscp() -> (u64, u32) {
+    let low: u32;
+    let high: u32;
+    let auxiliary: u32;
+    unsafe {
+        asm!("rdtscp", out("eax") low, out("edx") high, out("ecx") auxiliary);
+    }
+    ((u64::from(high) << 32) | u64::from(low), auxiliary)
+}
+
 pub fn sti() {
     unsafe {
         asm!("sti");
     }
 }
+
+pub fn swapgs() {
+    unsafe {
+        asm!("swapgs");
+    }
+}
+
+pub fn wrmsr(index: u32, value: u64) {
+    let low = (value & 0xFFFF_FFFF) as u32;
+    let high = (value >> 32) as u32;
+    unsafe {
+        asm!("wrmsr", in("ecx") index, in("eax") low, in("edx") high);
+    }
+}
@@ -0,0 +1,253 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::ptr;
+
+const SELECT: u64 = 0x00;
+const WINDOW: u64 = 0x10;
+
+const ID: u32 = 0x00;
+const VERSION: u32 = 0x01;
+const REDIRECTION_TABLE: u32 = 0x10;
+
+const LOGICAL_DESTINATION: u32 = 1 << 11;
+const DELIVERY_PENDING: u32 = 1 << 12;
+const ACTIVE_LOW: u32 = 1 << 13;
+const LEVEL_TRIGGERED: u32 = 1 << 15;
+const MASKED: u32 = 1 << 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Polarity {
+    High,
+    Low,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Trigger {
+    Edge,
+    Level,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Delivery {
+    Fixed = 0b000,
+    LowestPriority = 0b001,
+    SystemManagement = 0b010,
+    NonMaskable = 0b100,
+    Init = 0b101,
+    External = 0b111,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Redirection {
+    pub vector: u8,
+    pub delivery: Delivery,
+    pub logical: bool,
+    pub polarity: Polarity,
+    pub trigger: Trigger,
+    pub masked: bool,
+    pub destination: u8,
+}
+
+impl Redirection {
+    #[must_use]
+    pub fn new(vector: u8, destination: u8, polarity: Polarity, trigger: Trigger) -> Self {
+        Self {
+            vector,
+            delivery: Delivery::Fixed,
+            logical: false,
+            polarity,
+            trigger,
+            masked: false,
+            destination,
+        }
+    }
+
+    #[must_use]
+    pub fn bits(&self) -> u64 {
+        let mut value = u32::from(self.vector) | ((self.delivery as u32) << 8);
+
+        if self.logical {
+            value |= LOGICAL_DESTINATION;
+        }
+
+        if self.polarity == Polarity::Low {
+            value |= ACTIVE_LOW;
+        }
+
+        if self.trigger == Trigger::Level {
+            value |= LEVEL_TRIGGERED;
+        }
+
+        if self.masked {
+            value |= MASKED;
+        }
+
+        u64::from(value) | (u64::from(self.destination) << 56)
+    }
+
+    #[must_use]
+    pub fn from_bits(value: u64) -> Self {
+        let destination = (value >> 56) as u8;
+        let value = (value & 0xFFFF_FFFF) as u32;
+
+        let delivery = match (value >> 8) & 0b111 {
+            0b001 => Delivery::LowestPriority,
+            0b010 => Delivery::SystemManagement,
+            0b100 => Delivery::NonMaskable,
+            0b101 => Delivery::Init,
+            0b111 => Delivery::External,
+            _ => Delivery::Fixed,
+        };
+
+        Self {
+            vector: (value & 0xFF) as u8,
+            delivery,
+            logical: value & LOGICAL_DESTINATION != 0,
+            polarity: if value & ACTIVE_LOW != 0 {
+                Polarity::Low
+            } else {
+                Polarity::High
+            },
+            trigger: if value & LEVEL_TRIGGERED != 0 {
+                Trigger::Level
+            } else {
+                Trigger::Edge
+            },
+            masked: value & MASKED != 0,
+            destination,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct IoApic {
+    base: u64,
+    interrupt_base: u32,
+}
+
+impl IoApic {
+    #[must_use]
+    pub const fn new(base: u64, interrupt_base: u32) -> Self {
+        Self {
+            base,
+            interrupt_base,
+        }
+    }
+
+    fn read(&self, register: u32) -> u32 {
+        unsafe {
+            ptr::write_volatile((self.base + SELECT) as *mut u32, register);
+            ptr::read_volatile((self.base + WINDOW) as *const u32)
+        }
+    }
+
+    fn write(&self, register: u32, value: u32) {
+        unsafe {
+            ptr::write_volatile((self.base + SELECT) as *mut u32, register);
+            ptr::write_volatile((self.base + WINDOW) as *mut u32, value);
+        }
+    }
+
+    #[must_use]
+    pub fn id(&self) -> u8 {
+        ((self.read(ID) >> 24) & 0xF) as u8
+    }
+
+    #[must_use]
+    pub fn version(&self) -> u8 {
+        (self.read(VERSION) & 0xFF) as u8
+    }
+
+    #[must_use]
+    pub fn interrupt_base(&self) -> u32 {
+        self.interrupt_base
+    }
+
+    #[must_use]
+    pub fn entry_count(&self) -> u32 {
+        ((self.read(VERSION) >> 16) & 0xFF) + 1
+    }
+
+    #[must_use]
+    pub fn handles(&self, interrupt: u32) -> bool {
+        (self.interrupt_base..self.interrupt_base + self.entry_count()).contains(&interrupt)
+    }
+
+    fn register(&self, interrupt: u32) -> u32 {
+        REDIRECTION_TABLE + (interrupt - self.interrupt_base) * 2
+    }
+
+    #[must_use]
+    pub fn redirection(&self, interrupt: u32) -> Redirection {
+        let register = self.register(interrupt);
+        let low = u64::from(self.read(register));
+        let high = u64::from(self.read(register + 1));
+
+        Redirection::from_bits((high << 32) | low)
+    }
+
+    pub fn set_redirection(&self, interrupt: u32, redirection: &Redirection) {
+        let register = self.register(interrupt);
+        let value = redirection.bits();
+
+        self.write(register, MASKED);
+        self.write(register + 1, (value >> 32) as u32);
+        self.write(register, (value & 0xFFFF_FFFF) as u32);
+    }
+
+    pub fn mask(&self, interrupt: u32) {
+        let register = self.register(interrupt);
+        let low = self.read(register);
+        self.write(register, low | MASKED);
+    }
+
+    pub fn unmask(&self, interrupt: u32) {
+        let register = self.register(interrupt);
+        let low = self.read(register);
+        self.write(register, low & !MASKED);
+    }
+
+    #[must_use]
+    pub fn is_pending(&self, interrupt: u32) -> bool {
+        self.read(self.register(interrupt)) & DELIVERY_PENDING != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redirection_bits() {
+        let redirection = Redirection::new(0x21, 0x01, Polarity::High, Trigger::Edge);
+        assert_eq!(redirection.bits(), 0x0100_0000_0000_0021);
+    }
+
+    #[test]
+    fn test_redirection_level_low() {
+        let redirection = Redirection::new(0x30, 0x00, Polarity::Low, Trigger::Level);
+        assert_eq!(redirection.bits(), 0x0000_0000_0000_A030);
+    }
+
+    #[test]
+    fn test_redirection_round_trip() {
+        let mut redirection = Redirection::new(0x28, 0x03, Polarity::Low, Trigger::Level);
+        redirection.masked = true;
+        redirection.delivery = Delivery::LowestPriority;
+        assert_eq!(Redirection::from_bits(redirection.bits()), redirection);
+    }
+}
@@ -0,0 +1,412 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+pub const PAGE_SIZE: u64 = 0x1000;
+
+const ADDRESS_MASK: u64 = 0x000F_FFFF_FFFF_F000;
+const ENTRY_COUNT: usize = 512;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    AlreadyMapped,
+    HugePage,
+    NotMapped,
+    OutOfMemory,
+}
+
+pub trait FrameAllocator {
+    fn allocate(&mut self) -> Option<u64>;
+}
+
+#[derive(Clone, Copy, Default)]
+#[repr(transparent)]
+pub struct Entry(pub u64);
+
+impl Entry {
+    pub const PRESENT: u64 = 1 << 0;
+    pub const WRITABLE: u64 = 1 << 1;
+    pub const USER: u64 = 1 << 2;
+    pub const WRITE_THROUGH: u64 = 1 << 3;
+    pub const CACHE_DISABLE: u64 = 1 << 4;
+    pub const ACCESSED: u64 = 1 << 5;
+    pub const DIRTY: u64 = 1 << 6;
+    pub const HUGE: u64 = 1 << 7;
+    pub const GLOBAL: u64 = 1 << 8;
+    pub const NO_EXECUTE: u64 = 1 << 63;
+
+    #[must_use]
+    pub fn new(address: u64, flags: u64) -> Self {
+        Self((address & ADDRESS_MASK) | flags)
+    }
+
+    #[must_use]
+    pub fn address(&self) -> u64 {
+        self.0 & ADDRESS_MASK
+    }
+
+    #[must_use]
+    pub fn flags(&self) -> u64 {
+        self.0 & !ADDRESS_MASK
+    }
+
+    #[must_use]
+    pub fn is_present(&self) -> bool {
+        self.0 & Self::PRESENT != 0
+    }
+
+    #[must_use]
+    pub fn is_huge(&self) -> bool {
+        self.0 & Self::HUGE != 0
+    }
+}
+
+#[repr(C, align(4096))]
+pub struct Table {
+    entries: [Entry; ENTRY_COUNT],
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Table {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: [Entry(0); ENTRY_COUNT],
+        }
+    }
+
+    #[must_use]
+    pub fn entry(&self, index: usize) -> Entry {
+        self.entries[index]
+    }
+
+    pub fn set_entry(&mut self, index: usize, entry: Entry) {
+        self.entries[index] = entry;
+    }
+}
+
+fn indices(address: u64) -> [usize; 4] {
+    let index = |shift: u64| ((address >> shift) & 0x1FF) as usize;
+
+    [index(39), index(30), index(21), index(12)]
+}
+
+pub struct Mapper {
+    offset: u64,
+    root: u64,
+}
+
+impl Mapper {
+    /// # Safety
+    ///
+    /// `root` and every table it reaches must be mapped at `offset` and owned by this mapper.
+    #[must_use]
+    pub const unsafe fn new(offset: u64, root: u64) -> Self {
+        Self {
+            offset,
+            root: root & ADDRESS_MASK,
+        }
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    fn table(&self, physical: u64) -> &mut Table {
+        unsafe { &mut *((physical + self.offset) as *mut Table) }
+    }
+
+    #[must_use]
+    pub fn translate(&self, address: u64) -> Option<u64> {
+        let mut table = self.table(self.root);
+        let indices = indices(address);
+
+        for (level, &index) in indices.iter().enumerate() {
+            let entry = table.entry(index);
+
+            if !entry.is_present() {
+                return None;
+            }
+
+            if level == 3 {
+                return Some(entry.address() + (address & (PAGE_SIZE - 1)));
+            }
+
+            if entry.is_huge() {
+                let size = PAGE_SIZE << (9 * (3 - level));
+                let base = entry.address() & !(size - 1);
+                return Some(base + (address & (size - 1)));
+            }
+
+            table = self.table(entry.address());
+        }
+
+        None
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the page is already mapped or a table cannot be allocated.
+    pub fn map(
+        &self,
+        address: u64,
+        physical: u64,
+        flags: u64,
+        allocator: &mut impl FrameAllocator,
+    ) -> Result<(), Error> {
+        let mut table = self.table(self.root);
+        let indices = indices(address);
+        let parent_flags = Entry::PRESENT | Entry::WRITABLE | (flags & Entry::USER);
+
+        for &index in &indices[..3] {
+            let entry = table.entry(index);
+
+            if entry.is_present() {
+                if entry.is_huge() {
+                    return Err(Error::HugePage);
+                }
+
+                if entry.flags() & parent_flags != parent_flags {
+                    table.set_entry(
+                        index,
+                        Entry::new(entry.address(), entry.flags() | parent_flags),
+                    );
+                }
+            } else {
+                let frame = allocator.allocate().ok_or(Error::OutOfMemory)?;
+                *self.table(frame) = Table::new();
+                table.set_entry(index, Entry::new(frame, parent_flags));
+            }
+
+            table = self.table(table.entry(index).address());
+        }
+
+        if table.entry(indices[3]).is_present() {
+            return Err(Error::AlreadyMapped);
+        }
+
+        table.set_entry(indices[3], Entry::new(physical, flags | Entry::PRESENT));
+
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the page is not mapped.
+    pub fn update(
+        &self,
+        address: u64,
+        flags: u64,
+        allocator: &mut impl FrameAllocator,
+    ) -> Result<(), Error> {
+        let mut table = self.table(self.root);
+        let indices = indices(address);
+
+        for (level, &index) in indices[..3].iter().enumerate() {
+            let entry = table.entry(index);
+
+            if !entry.is_present() {
+                return Err(Error::NotMapped);
+            }
+
+            if entry.is_huge() {
+                let frame = allocator.allocate().ok_or(Error::OutOfMemory)?;
+                self.split(frame, entry, level);
+                table.set_entry(index, Entry::new(frame, entry.flags() & !Entry::HUGE));
+            }
+
+            table = self.table(table.entry(index).address());
+        }
+
+        let entry = table.entry(indices[3]);
+
+        if !entry.is_present() {
+            return Err(Error::NotMapped);
+        }
+
+        table.set_entry(
+            indices[3],
+            Entry::new(entry.address(), flags | Entry::PRESENT),
+        );
+
+        Ok(())
+    }
+
+    fn split(&self, frame: u64, entry: Entry, level: usize) {
+        let size = PAGE_SIZE << (9 * (2 - level));
+        let base = entry.address() & !((PAGE_SIZE << (9 * (3 - level))) - 1);
+        let flags = if level == 2 {
+            entry.flags() & !Entry::HUGE
+        } else {
+            entry.flags()
+        };
+        let table = self.table(frame);
+
+        for index in 0..ENTRY_COUNT {
+            table.set_entry(index, Entry::new(base + index as u64 * size, flags));
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the page is not mapped or lies within a huge page.
+    pub fn unmap(&self, address: u64) -> Result<u64, Error> {
+        let mut table = self.table(self.root);
+        let indices = indices(address);
+
+        for &index in &indices[..3] {
+            let entry = table.entry(index);
+
+            if !entry.is_present() {
+                return Err(Error::NotMapped);
+            }
+
+            if entry.is_huge() {
+                return Err(Error::HugePage);
+            }
+
+            table = self.table(entry.address());
+        }
+
+        let entry = table.entry(indices[3]);
+
+        if !entry.is_present() {
+            return Err(Error::NotMapped);
+        }
+
+        table.set_entry(indices[3], Entry(0));
+
+        Ok(entry.address())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::boxed::Box;
+    use std::vec::Vec;
+
+    struct Frames(Vec<Box<Table>>);
+
+    impl FrameAllocator for Frames {
+        fn allocate(&mut self) -> Option<u64> {
+            let table = Box::new(Table::new());
+            let address = &raw const *table as u64;
+            self.0.push(table);
+            Some(address)
+        }
+    }
+
+    fn create_mapper(frames: &mut Frames) -> Mapper {
+        let root = frames.allocate().unwrap();
+        unsafe { Mapper::new(0, root) }
+    }
+
+    #[test]
+    fn test_entry_address() {
+        let entry = Entry::new(0xFEE0_0000, Entry::PRESENT | Entry::WRITABLE);
+        assert_eq!(entry.address(), 0xFEE0_0000);
+        assert_eq!(entry.flags(), 0x3);
+    }
+
+    #[test]
+    fn test_indices() {
+        assert_eq!(indices(0xFFFF_FFFF_8000_0000), [511, 510, 0, 0]);
+        assert_eq!(indices(0x0000_0000_0020_1000), [0, 0, 1, 1]);
+    }
+
+    #[test]
+    fn test_map() {
+        let mut frames = Frames(Vec::new());
+        let mapper = create_mapper(&mut frames);
+
+        mapper
+            .map(0xFFFF_8000_0000_1000, 0x5000, Entry::WRITABLE, &mut frames)
+            .unwrap();
+
+        assert_eq!(mapper.translate(0xFFFF_8000_0000_1234), Some(0x5234));
+        assert_eq!(mapper.translate(0xFFFF_8000_0000_2000), None);
+        assert_eq!(frames.0.len(), 4);
+    }
+
+    #[test]
+    fn test_map_already_mapped() {
+        let mut frames = Frames(Vec::new());
+        let mapper = create_mapper(&mut frames);
+
+        mapper.map(0x1000, 0x5000, 0, &mut frames).unwrap();
+
+        assert_eq!(
+            mapper.map(0x1000, 0x6000, 0, &mut frames),
+            Err(Error::AlreadyMapped)
+        );
+    }
+
+    #[test]
+    fn test_update() {
+        let mut frames = Frames(Vec::new());
+        let mapper = create_mapper(&mut frames);
+
+        mapper
+            .map(0x1000, 0x5000, Entry::WRITABLE, &mut frames)
+            .unwrap();
+        mapper
+            .update(0x1000, Entry::CACHE_DISABLE, &mut frames)
+            .unwrap();
+
+        assert_eq!(mapper.translate(0x1234), Some(0x5234));
+        assert_eq!(
+            mapper.update(0x2000, Entry::CACHE_DISABLE, &mut frames),
+            Err(Error::NotMapped)
+        );
+    }
+
+    #[test]
+    fn test_update_huge_page() {
+        let mut frames = Frames(Vec::new());
+        let mapper = create_mapper(&mut frames);
+
+        mapper.map(0x1000, 0x5000, 0, &mut frames).unwrap();
+        let directory = mapper.table(mapper.root).entry(0).address();
+        let directory = mapper.table(directory).entry(0).address();
+        mapper
+            .table(directory)
+            .set_entry(1, Entry::new(0x4000_0000, Entry::PRESENT | Entry::HUGE));
+
+        mapper
+            .update(0x0020_3000, Entry::CACHE_DISABLE, &mut frames)
+            .unwrap();
+
+        assert_eq!(mapper.translate(0x0020_3456), Some(0x4000_3456));
+        assert_eq!(mapper.translate(0x003F_F000), Some(0x401F_F000));
+        assert!(!mapper.table(directory).entry(1).is_huge());
+    }
+
+    #[test]
+    fn test_unmap() {
+        let mut frames = Frames(Vec::new());
+        let mapper = create_mapper(&mut frames);
+
+        mapper.map(0x1000, 0x5000, 0, &mut frames).unwrap();
+
+        assert_eq!(mapper.unmap(0x1000), Ok(0x5000));
+        assert_eq!(mapper.translate(0x1000), None);
+        assert_eq!(mapper.unmap(0x1000), Err(Error::NotMapped));
+    }
+}
@@ -0,0 +1,255 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::port::Port;
+
+const CASCADE_LINE: u8 = 2;
+const END_OF_INTERRUPT: u8 = 0x20;
+const INITIALIZE: u8 = 0x11;
+const LINES: u8 = 16;
+const MODE_8086: u8 = 0x01;
+const READ_IRR: u8 = 0x0A;
+const READ_ISR: u8 = 0x0B;
+
+pub struct Chip {
+    command: Port<u8>,
+    data: Port<u8>,
+    offset: u8,
+}
+
+impl Chip {
+    #[must_use]
+    pub const fn new(address: u16, offset: u8) -> Self {
+        Self {
+            command: Port::new(address),
+            data: Port::new(address + 1),
+            offset,
+        }
+    }
+
+    #[must_use]
+    pub fn offset(&self) -> u8 {
+        self.offset
+    }
+
+    fn handles(&self, vector: u8) -> bool {
+        (self.offset..self.offset + 8).contains(&vector)
+    }
+
+    pub fn end_of_interrupt(&self) {
+        self.command.write(END_OF_INTERRUPT);
+    }
+
+    #[must_use]
+    pub fn mask(&self) -> u8 {
+        self.data.read()
+    }
+
+    pub fn set_mask(&self, mask: u8) {
+        self.data.write(mask);
+    }
+
+    #[must_use]
+    pub fn requested(&self) -> u8 {
+        self.command.write(READ_IRR);
+        self.command.read()
+    }
+
+    #[must_use]
+    pub fn in_service(&self) -> u8 {
+        self.command.write(READ_ISR);
+        self.command.read()
+    }
+}
+
+pub struct Controller {
+    primary: Chip,
+    secondary: Chip,
+}
+
+impl Controller {
+    #[must_use]
+    pub const fn new(primary_offset: u8, secondary_offset: u8) -> Self {
+        Self {
+            primary: Chip::new(0x20, primary_offset),
+            secondary: Chip::new(0xA0, secondary_offset),
+        }
+    }
+
+    pub fn init(&self) {
+        self.primary.command.write(INITIALIZE);
+        wait();
+        self.secondary.command.write(INITIALIZE);
+        wait();
+
+        self.primary.data.write(self.primary.offset);
+        wait();
+        self.secondary.data.write(self.secondary.offset);
+        wait();
+
+        self.primary.data.write(1 << CASCADE_LINE);
+        wait();
+        self.secondary.data.write(CASCADE_LINE);
+        wait();
+
+        self.primary.data.write(MODE_8086);
+        wait();
+        self.secondary.data.write(MODE_8086);
+        wait();
+
+        self.primary.set_mask(!(1 << CASCADE_LINE));
+        self.secondary.set_mask(0xFF);
+    }
+
+    pub fn disable(&self) {
+        self.primary.set_mask(0xFF);
+        self.secondary.set_mask(0xFF);
+    }
+
+    #[must_use]
+    pub fn primary(&self) -> &Chip {
+        &self.primary
+    }
+
+    #[must_use]
+    pub fn secondary(&self) -> &Chip {
+        &self.secondary
+    }
+
+    #[must_use]
+    pub fn handles(&self, vector: u8) -> bool {
+        self.primary.handles(vector) || self.secondary.handles(vector)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `line` is not below 16.
+    #[must_use]
+    pub fn vector(&self, line: u8) -> u8 {
+        let (chip, bit) = self.chip(line);
+        chip.offset + bit
+    }
+
+    #[must_use]
+    pub fn line(&self, vector: u8) -> Option<u8> {
+        if self.primary.handles(vector) {
+            Some(vector - self.primary.offset)
+        } else if self.secondary.handles(vector) {
+            Some(vector - self.secondary.offset + 8)
+        } else {
+            None
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `line` is not below 16.
+    pub fn mask(&self, line: u8) {
+        let (chip, bit) = self.chip(line);
+        chip.set_mask(chip.mask() | (1 << bit));
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `line` is not below 16.
+    pub fn unmask(&self, line: u8) {
+        let (chip, bit) = self.chip(line);
+        chip.set_mask(chip.mask() & !(1 << bit));
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `line` is not below 16.
+    pub fn end_of_interrupt(&self, line: u8) {
+        assert!(line < LINES, "Failed to validate the IRQ line.");
+
+        if line >= 8 {
+            self.secondary.end_of_interrupt();
+        }
+
+        self.primary.end_of_interrupt();
+    }
+
+    #[must_use]
+    pub fn is_spurious(&self, line: u8) -> bool {
+        match line {
+            7 => self.primary.in_service() & 0x80 == 0,
+            15 => {
+                let spurious = self.secondary.in_service() & 0x80 == 0;
+
+                if spurious {
+                    self.primary.end_of_interrupt();
+                }
+
+                spurious
+            }
+            _ => false,
+        }
+    }
+
+    fn chip(&self, line: u8) -> (&Chip, u8) {
+        assert!(line < LINES, "Failed to validate the IRQ line.");
+
+        if line < 8 {
+            (&self.primary, line)
+        } else {
+            (&self.secondary, line - 8)
+        }
+    }
+}
+
+fn wait() {
+    Port::<u8>::new(0x80).write(0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Controller;
+
+    #[test]
+    fn test_vector() {
+        let controller = Controller::new(32, 40);
+        assert_eq!(controller.vector(0), 32);
+        assert_eq!(controller.vector(7), 39);
+        assert_eq!(controller.vector(8), 40);
+        assert_eq!(controller.vector(15), 47);
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to validate the IRQ line.")]
+    fn test_vector_out_of_range() {
+        let controller = Controller::new(32, 40);
+        let _ = controller.vector(16);
+    }
+
+    #[test]
+    fn test_line() {
+        let controller = Controller::new(32, 40);
+        assert_eq!(controller.line(31), None);
+        assert_eq!(controller.line(32), Some(0));
+        assert_eq!(controller.line(47), Some(15));
+        assert_eq!(controller.line(48), None);
+    }
+
+    #[test]
+    fn test_handles() {
+        let controller = Controller::new(32, 40);
+        assert!(!controller.handles(8));
+        assert!(controller.handles(39));
+        assert!(controller.handles(40));
+        assert!(!controller.handles(48));
+    }
+}
@@ -0,0 +1,153 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::port::{Port, WriteOnlyPort};
+
+pub const FREQUENCY: u32 = 1_193_182;
+
+const GATE: u8 = 1 << 0;
+const SPEAKER: u8 = 1 << 1;
+const OUTPUT: u8 = 1 << 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Channel {
+    Zero = 0,
+    One = 1,
+    Two = 2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    InterruptOnTerminalCount = 0,
+    OneShot = 1,
+    RateGenerator = 2,
+    SquareWave = 3,
+    SoftwareStrobe = 4,
+    HardwareStrobe = 5,
+}
+
+#[must_use]
+pub fn divisor(frequency: u32) -> u16 {
+    let divisor = FREQUENCY / frequency.max(1);
+    u16::try_from(divisor).unwrap_or(u16::MAX).max(1)
+}
+
+fn command(channel: Channel, mode: Mode) -> u8 {
+    ((channel as u8) << 6) | (0b11 << 4) | ((mode as u8) << 1)
+}
+
+pub struct Timer {
+    channels: [Port<u8>; 3],
+    command: WriteOnlyPort<u8>,
+    control: Port<u8>,
+}
+
+impl Default for Timer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Timer {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            channels: [Port::new(0x40), Port::new(0x41), Port::new(0x42)],
+            command: WriteOnlyPort::new(0x43),
+            control: Port::new(0x61),
+        }
+    }
+
+    pub fn configure(&self, channel: Channel, mode: Mode, count: u16) {
+        let [low, high] = count.to_le_bytes();
+        let port = &self.channels[channel as usize];
+
+        self.command.write(command(channel, mode));
+        port.write(low);
+        port.write(high);
+    }
+
+    #[must_use]
+    pub fn count(&self, channel: Channel) -> u16 {
+        let port = &self.channels[channel as usize];
+
+        self.command.write((channel as u8) << 6);
+        let low = port.read();
+        let high = port.read();
+
+        u16::from_le_bytes([low, high])
+    }
+
+    pub fn set_periodic(&self, frequency: u32) {
+        self.configure(Channel::Zero, Mode::RateGenerator, divisor(frequency));
+    }
+
+    pub fn set_one_shot(&self, ticks: u16) {
+        self.configure(Channel::Zero, Mode::InterruptOnTerminalCount, ticks);
+    }
+
+    pub fn start_gate(&self, ticks: u16) {
+        let control = self.control.read();
+        self.control.write((control & !SPEAKER) | GATE);
+
+        self.configure(Channel::Two, Mode::InterruptOnTerminalCount, ticks);
+    }
+
+    #[must_use]
+    pub fn is_gate_expired(&self) -> bool {
+        self.control.read() & OUTPUT != 0
+    }
+
+    pub fn stop_gate(&self) {
+        let control = self.control.read();
+        self.control.write(control & !GATE);
+    }
+
+    pub fn play(&self, frequency: u32) {
+        self.configure(Channel::Two, Mode::SquareWave, divisor(frequency));
+
+        let control = self.control.read();
+        self.control.write(control | GATE | SPEAKER);
+    }
+
+    pub fn silence(&self) {
+        let control = self.control.read();
+        self.control.write(control & !(GATE | SPEAKER));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_divisor() {
+        assert_eq!(divisor(1000), 1193);
+        assert_eq!(divisor(100), 11931);
+        assert_eq!(divisor(1), u16::MAX);
+        assert_eq!(divisor(0), u16::MAX);
+        assert_eq!(divisor(FREQUENCY), 1);
+        assert_eq!(divisor(u32::MAX), 1);
+    }
+
+    #[test]
+    fn test_command() {
+        assert_eq!(command(Channel::Zero, Mode::RateGenerator), 0x34);
+        assert_eq!(command(Channel::Zero, Mode::InterruptOnTerminalCount), 0x30);
+        assert_eq!(command(Channel::Two, Mode::InterruptOnTerminalCount), 0xB0);
+        assert_eq!(command(Channel::Two, Mode::SquareWave), 0xB6);
+    }
+}
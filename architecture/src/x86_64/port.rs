@@ -0,0 +1,177 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::marker::PhantomData;
+
+use super::instruction;
+
+pub trait PortValue: Copy {
+    fn read(address: u16) -> Self;
+    fn write(address: u16, value: Self);
+    fn read_string(address: u16, buffer: &mut [Self]);
+    fn write_string(address: u16, buffer: &[Self]);
+}
+
+impl PortValue for u8 {
+    fn read(address: u16) -> Self {
+        instruction::inb(address)
+    }
+
+    fn write(address: u16, value: Self) {
+        instruction::outb(address, value);
+    }
+
+    fn read_string(address: u16, buffer: &mut [Self]) {
+        instruction::insb(address, buffer);
+    }
+
+    fn write_string(address: u16, buffer: &[Self]) {
+        instruction::outsb(address, buffer);
+    }
+}
+
+impl PortValue for u16 {
+    fn read(address: u16) -> Self {
+        instruction::inw(address)
+    }
+
+    fn write(address: u16, value: Self) {
+        instruction::outw(address, value);
+    }
+
+    fn read_string(address: u16, buffer: &mut [Self]) {
+        instruction::insw(address, buffer);
+    }
+
+    fn write_string(address: u16, buffer: &[Self]) {
+        instruction::outsw(address, buffer);
+    }
+}
+
+impl PortValue for u32 {
+    fn read(address: u16) -> Self {
+        instruction::inl(address)
+    }
+
+    fn write(address: u16, value: Self) {
+        instruction::outl(address, value);
+    }
+
+    fn read_string(address: u16, buffer: &mut [Self]) {
+        instruction::insl(address, buffer);
+    }
+
+    fn write_string(address: u16, buffer: &[Self]) {
+        instruction::outsl(address, buffer);
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Port<T> {
+    address: u16,
+    phantom: PhantomData<T>,
+}
+
+impl<T: PortValue> Port<T> {
+    #[must_use]
+    pub const fn new(address: u16) -> Self {
+        Self {
+            address,
+            phantom: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub const fn address(&self) -> u16 {
+        self.address
+    }
+
+    #[must_use]
+    pub fn read(&self) -> T {
+        T::read(self.address)
+    }
+
+    pub fn write(&self, value: T) {
+        T::write(self.address, value);
+    }
+
+    pub fn read_string(&self, buffer: &mut [T]) {
+        T::read_string(self.address, buffer);
+    }
+
+    pub fn write_string(&self, buffer: &[T]) {
+        T::write_string(self.address, buffer);
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct ReadOnlyPort<T> {
+    address: u16,
+    phantom: PhantomData<T>,
+}
+
+impl<T: PortValue> ReadOnlyPort<T> {
+    #[must_use]
+    pub const fn new(address: u16) -> Self {
+        Self {
+            address,
+            phantom: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub const fn address(&self) -> u16 {
+        self.address
+    }
+
+    #[must_use]
+    pub fn read(&self) -> T {
+        T::read(self.address)
+    }
+
+    pub fn read_string(&self, buffer: &mut [T]) {
+        T::read_string(self.address, buffer);
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct WriteOnlyPort<T> {
+    address: u16,
+    phantom: PhantomData<T>,
+}
+
+impl<T: PortValue> WriteOnlyPort<T> {
+    #[must_use]
+    pub const fn new(address: u16) -> Self {
+        Self {
+            address,
+            phantom: PhantomData,
+        }
+    }
+
+    #[must_use]
+    pub const fn address(&self) -> u16 {
+        self.address
+    }
+
+    pub fn write(&self, value: T) {
+        T::write(self.address, value);
+    }
+
+    pub fn write_string(&self, buffer: &[T]) {
+        T::write_string(self.address, buffer);
+    }
+}
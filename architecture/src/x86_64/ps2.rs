@@ -0,0 +1,247 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::port::{Port, ReadOnlyPort};
+
+const DATA: u16 = 0x60;
+
+const STATUS: u16 = 0x64;
+
+const OUTPUT_FULL: u8 = 0x01;
+
+const INPUT_FULL: u8 = 0x02;
+
+const RELEASE: u8 = 0x80;
+
+const EXTENDED: u8 = 0xE0;
+
+const LEFT_SHIFT: u8 = 0x2A;
+
+const RIGHT_SHIFT: u8 = 0x36;
+
+const CONTROL: u8 = 0x1D;
+
+const CAPS_LOCK: u8 = 0x3A;
+
+const LOWER: &[u8; 58] =
+    b"\0\x1b1234567890-=\x08\tqwertyuiop[]\n\0asdfghjkl;'`\0\\zxcvbnm,./\0*\0 ";
+
+const UPPER: &[u8; 58] =
+    b"\0\x1b!@#$%^&*()_+\x08\tQWERTYUIOP{}\n\0ASDFGHJKL:\"~\0|ZXCVBNM<>?\0*\0 ";
+
+pub struct Controller {
+    data: Port<u8>,
+    status: ReadOnlyPort<u8>,
+}
+
+impl Default for Controller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Controller {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            data: Port::new(DATA),
+            status: ReadOnlyPort::new(STATUS),
+        }
+    }
+
+    #[must_use]
+    pub fn has_output(&self) -> bool {
+        self.status.read() & OUTPUT_FULL != 0
+    }
+
+    #[must_use]
+    pub fn try_read(&self) -> Option<u8> {
+        self.has_output().then(|| self.data.read())
+    }
+
+    #[must_use]
+    pub fn is_input_full(&self) -> bool {
+        self.status.read() & INPUT_FULL != 0
+    }
+
+    pub fn write(&self, value: u8) {
+        while self.is_input_full() {}
+
+        self.data.write(value);
+    }
+
+    pub fn flush(&self) {
+        while self.try_read().is_some() {}
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Decoder {
+    modifiers: u8,
+    extended: bool,
+}
+
+impl Decoder {
+    const SHIFT: u8 = 1 << 0;
+    const CONTROL: u8 = 1 << 1;
+    const CAPS_LOCK: u8 = 1 << 2;
+
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            modifiers: 0,
+            extended: false,
+        }
+    }
+
+    fn set(&mut self, modifier: u8, enabled: bool) {
+        if enabled {
+            self.modifiers |= modifier;
+        } else {
+            self.modifiers &= !modifier;
+        }
+    }
+
+    fn has(self, modifier: u8) -> bool {
+        self.modifiers & modifier != 0
+    }
+
+    pub fn feed(&mut self, scancode: u8) -> Option<char> {
+        if scancode == EXTENDED {
+            self.extended = true;
+            return None;
+        }
+
+        let extended = self.extended;
+        self.extended = false;
+
+        let pressed = scancode & RELEASE == 0;
+        let code = scancode & !RELEASE;
+
+        match code {
+            LEFT_SHIFT | RIGHT_SHIFT if !extended => self.set(Self::SHIFT, pressed),
+            CONTROL => self.set(Self::CONTROL, pressed),
+            CAPS_LOCK if pressed => self.modifiers ^= Self::CAPS_LOCK,
+            _ if pressed && !extended => return self.translate(code),
+            _ => {}
+        }
+
+        None
+    }
+
+    fn translate(self, code: u8) -> Option<char> {
+        let lower = *LOWER.get(usize::from(code))?;
+        if lower == 0 {
+            return None;
+        }
+
+        let upper = UPPER[usize::from(code)];
+        let shift = self.has(Self::SHIFT);
+        let byte = if lower.is_ascii_lowercase() {
+            if shift == self.has(Self::CAPS_LOCK) {
+                lower
+            } else {
+                upper
+            }
+        } else if shift {
+            upper
+        } else {
+            lower
+        };
+
+        if self.has(Self::CONTROL) && byte.is_ascii_alphabetic() {
+            return Some(char::from(byte.to_ascii_lowercase() - b'a' + 1));
+        }
+
+        Some(char::from(byte))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::vec;
+    use std::vec::Vec;
+
+    fn type_keys(decoder: &mut Decoder, scancodes: &[u8]) -> Vec<char> {
+        scancodes
+            .iter()
+            .filter_map(|&scancode| decoder.feed(scancode))
+            .collect()
+    }
+
+    #[test]
+    fn test_letters() {
+        let mut decoder = Decoder::new();
+
+        assert_eq!(
+            type_keys(&mut decoder, &[0x23, 0xA3, 0x17, 0x97, 0x39, 0xB9]),
+            vec!['h', 'i', ' ']
+        );
+    }
+
+    #[test]
+    fn test_shift() {
+        let mut decoder = Decoder::new();
+
+        assert_eq!(
+            type_keys(&mut decoder, &[0x2A, 0x1E, 0x02, 0xAA, 0x1E, 0x02]),
+            vec!['A', '!', 'a', '1']
+        );
+    }
+
+    #[test]
+    fn test_caps_lock() {
+        let mut decoder = Decoder::new();
+
+        assert_eq!(
+            type_keys(&mut decoder, &[0x3A, 0xBA, 0x1E, 0x02, 0x36, 0x1E, 0xB6]),
+            vec!['A', '1', 'a']
+        );
+    }
+
+    #[test]
+    fn test_control() {
+        let mut decoder = Decoder::new();
+
+        assert_eq!(
+            type_keys(&mut decoder, &[0x1D, 0x14, 0x9D, 0x14]),
+            vec!['\x14', 't']
+        );
+    }
+
+    #[test]
+    fn test_special_keys() {
+        let mut decoder = Decoder::new();
+
+        assert_eq!(
+            type_keys(&mut decoder, &[0x1C, 0x0E, 0x0F, 0x01, 0x3B, 0x58]),
+            vec!['\n', '\x08', '\t', '\x1b']
+        );
+    }
+
+    #[test]
+    fn test_extended() {
+        let mut decoder = Decoder::new();
+
+        assert_eq!(
+            type_keys(&mut decoder, &[0xE0, 0x48, 0xE0, 0xC8, 0xE0, 0x2A, 0x1E]),
+            vec!['a']
+        );
+    }
+}
@@ -15,6 +15,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use super::gdt::Selector;
+use super::instruction;
 
 use core::arch::asm;
 
@@ -45,6 +46,76 @@ impl CS {
     }
 }
 
+pub struct CR0;
+
+impl CR0 {
+    #[must_use]
+    pub fn get() -> u64 {
+        let value: u64;
+        unsafe {
+            asm!("mov {0}, cr0", out(reg) value);
+        }
+        value
+    }
+
+    pub fn set(value: u64) {
+        unsafe {
+            asm!("mov cr0, {0}", in(reg) value);
+        }
+    }
+}
+
+pub struct CR2;
+
+impl CR2 {
+    #[must_use]
+    pub fn get() -> u64 {
+        let value: u64;
+        unsafe {
+            asm!("mov {0}, cr2", out(reg) value);
+        }
+        value
+    }
+}
+
+pub struct CR3;
+
+impl CR3 {
+    #[must_use]
+    pub fn get() -> u64 {
+        let value: u64;
+        unsafe {
+            asm!("mov {0}, cr3", out(reg) value);
+        }
+        value
+    }
+
+    pub fn set(value: u64) {
+        unsafe {
+            asm!("mov cr3, {0}", in(reg) value);
+        }
+    }
+}
+
+pub struct CR4;
+
+impl CR4 {
+    #[must_use]
+    pub fn get() -> u64 {
+        let value: u64;
+        unsafe {
+            asm!("mov {0}, cr4", out(reg) value);
+        }
+        value
+    }
+
+    pub fn set(value: u64) {
+        unsafe {
+            asm!("mov cr4, {0}", in(reg) value);
+        }
+    }
+}
+
 pub struct DS;
 
 impl DS {
@@ -121,6 +192,36 @@ impl GS {
     }
 }
 
+pub struct GsBase;
+
+impl GsBase {
+    pub const MSR: u32 = 0xC000_0101;
+
+    #[must_use]
+    pub fn get() -> u64 {
+        instruction::rdmsr(Self::MSR)
+    }
+
+    pub fn set(value: u64) {
+        instruction::wrmsr(Self::MSR, value);
+    }
+}
+
+pub struct KernelGsBase;
+
+impl KernelGsBase {
+    pub const MSR: u32 = 0xC000_0102;
+
+    #[must_use]
+    pub fn get() -> u64 {
+        instruction::rdmsr(Self::MSR)
+    }
+
+    pub fn set(value: u64) {
+        instruction::wrmsr(Self::MSR, value);
+    }
+}
+
 pub struct SS;
 
 impl SS {
@@ -139,3 +240,23 @@ impl SS {
         }
     }
 }
+
+pub struct RFLAGS;
+
+impl RFLAGS {
+    pub const INTERRUPT_FLAG: u64 = 1 << 9;
+
+    #[must_use]
+    pub fn get() -> u64 {
+        let value: u64;
+        unsafe {
+            asm!("pushfq", "pop {0}", out(reg) value);
+        }
+        value
+    }
+
+    #[must_use]
+    pub fn interrupts_enabled() -> bool {
+        Self::get() & Self::INTERRUPT_FLAG != 0
+    }
+}
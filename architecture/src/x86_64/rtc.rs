@@ -0,0 +1,295 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::instruction;
+use super::port::{Port, WriteOnlyPort};
+
+const NMI_DISABLE: u8 = 1 << 7;
+
+const SECONDS: u8 = 0x00;
+const ALARM_SECONDS: u8 = 0x01;
+const MINUTES: u8 = 0x02;
+const ALARM_MINUTES: u8 = 0x03;
+const HOURS: u8 = 0x04;
+const ALARM_HOURS: u8 = 0x05;
+const DAY: u8 = 0x07;
+const MONTH: u8 = 0x08;
+const YEAR: u8 = 0x09;
+const STATUS_A: u8 = 0x0A;
+const STATUS_B: u8 = 0x0B;
+const STATUS_C: u8 = 0x0C;
+
+const UPDATE_IN_PROGRESS: u8 = 1 << 7;
+const HOUR_FORMAT_24: u8 = 1 << 1;
+const BINARY_MODE: u8 = 1 << 2;
+const PM: u8 = 1 << 7;
+
+pub const UPDATE_INTERRUPT: u8 = 1 << 4;
+pub const ALARM_INTERRUPT: u8 = 1 << 5;
+pub const PERIODIC_INTERRUPT: u8 = 1 << 6;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Reading {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Registers {
+    second: u8,
+    minute: u8,
+    hour: u8,
+    day: u8,
+    month: u8,
+    year: u8,
+    century: u8,
+}
+
+fn from_bcd(value: u8) -> u8 {
+    (value >> 4) * 10 + (value & 0x0F)
+}
+
+fn to_bcd(value: u8) -> u8 {
+    ((value / 10) << 4) | (value % 10)
+}
+
+fn decode(registers: Registers, status: u8) -> Reading {
+    let binary = status & BINARY_MODE != 0;
+    let convert = |value: u8| if binary { value } else { from_bcd(value) };
+
+    let pm = registers.hour & PM != 0;
+    let mut hour = convert(registers.hour & !PM);
+
+    if status & HOUR_FORMAT_24 == 0 {
+        hour = match (hour, pm) {
+            (12, false) => 0,
+            (12, true) => 12,
+            (hour, true) => hour + 12,
+            (hour, false) => hour,
+        };
+    }
+
+    let year = u16::from(convert(registers.year));
+    let century = match registers.century {
+        0 => 20,
+        century => u16::from(convert(century)),
+    };
+
+    Reading {
+        year: century * 100 + year,
+        month: convert(registers.month),
+        day: convert(registers.day),
+        hour,
+        minute: convert(registers.minute),
+        second: convert(registers.second),
+    }
+}
+
+pub struct Clock {
+    index: WriteOnlyPort<u8>,
+    data: Port<u8>,
+    century: Option<u8>,
+}
+
+impl Clock {
+    #[must_use]
+    pub const fn new(century: Option<u8>) -> Self {
+        Self {
+            index: WriteOnlyPort::new(0x70),
+            data: Port::new(0x71),
+            century,
+        }
+    }
+
+    fn read(&self, register: u8) -> u8 {
+        self.index.write(NMI_DISABLE | register);
+        self.data.read()
+    }
+
+    fn write(&self, register: u8, value: u8) {
+        self.index.write(NMI_DISABLE | register);
+        self.data.write(value);
+    }
+
+    #[must_use]
+    pub fn is_updating(&self) -> bool {
+        self.read(STATUS_A) & UPDATE_IN_PROGRESS != 0
+    }
+
+    fn registers(&self) -> Registers {
+        while self.is_updating() {
+            instruction::pause();
+        }
+
+        Registers {
+            second: self.read(SECONDS),
+            minute: self.read(MINUTES),
+            hour: self.read(HOURS),
+            day: self.read(DAY),
+            month: self.read(MONTH),
+            year: self.read(YEAR),
+            century: self.century.map_or(0, |register| self.read(register)),
+        }
+    }
+
+    #[must_use]
+    pub fn now(&self) -> Reading {
+        let mut registers = self.registers();
+
+        loop {
+            let next = self.registers();
+
+            if next == registers {
+                break;
+            }
+
+            registers = next;
+        }
+
+        decode(registers, self.read(STATUS_B))
+    }
+
+    fn encode(&self, value: u8) -> u8 {
+        if self.read(STATUS_B) & BINARY_MODE != 0 {
+            value
+        } else {
+            to_bcd(value)
+        }
+    }
+
+    fn encode_hour(&self, hour: u8) -> u8 {
+        if self.read(STATUS_B) & HOUR_FORMAT_24 != 0 {
+            return self.encode(hour);
+        }
+
+        match hour {
+            0 => self.encode(12),
+            12 => self.encode(12) | PM,
+            13.. => self.encode(hour - 12) | PM,
+            _ => self.encode(hour),
+        }
+    }
+
+    pub fn set_alarm(&self, hour: u8, minute: u8, second: u8) {
+        self.write(ALARM_HOURS, self.encode_hour(hour));
+        self.write(ALARM_MINUTES, self.encode(minute));
+        self.write(ALARM_SECONDS, self.encode(second));
+        self.enable_interrupts(ALARM_INTERRUPT);
+    }
+
+    pub fn set_periodic(&self, rate: u8) {
+        let rate = rate.clamp(3, 15);
+        let status = self.read(STATUS_A);
+        self.write(STATUS_A, (status & 0xF0) | rate);
+        self.enable_interrupts(PERIODIC_INTERRUPT);
+    }
+
+    pub fn enable_interrupts(&self, interrupts: u8) {
+        let status = self.read(STATUS_B);
+        self.write(STATUS_B, status | interrupts);
+    }
+
+    pub fn disable_interrupts(&self, interrupts: u8) {
+        let status = self.read(STATUS_B);
+        self.write(STATUS_B, status & !interrupts);
+    }
+
+    #[must_use]
+    pub fn acknowledge(&self) -> u8 {
+        self.read(STATUS_C)
+    }
+}
+
+#[must_use]
+pub fn periodic_frequency(rate: u8) -> u32 {
+    32768 >> (rate.clamp(3, 15) - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registers(hour: u8) -> Registers {
+        Registers {
+            second: 0x45,
+            minute: 0x30,
+            hour,
+            day: 0x29,
+            month: 0x02,
+            year: 0x24,
+            century: 0x20,
+        }
+    }
+
+    #[test]
+    fn test_bcd() {
+        assert_eq!(from_bcd(0x59), 59);
+        assert_eq!(to_bcd(59), 0x59);
+        assert_eq!(from_bcd(to_bcd(7)), 7);
+    }
+
+    #[test]
+    fn test_decode_bcd_24_hour() {
+        let reading = decode(registers(0x23), HOUR_FORMAT_24);
+        assert_eq!(
+            reading,
+            Reading {
+                year: 2024,
+                month: 2,
+                day: 29,
+                hour: 23,
+                minute: 30,
+                second: 45,
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_bcd_12_hour() {
+        assert_eq!(decode(registers(0x12), 0).hour, 0);
+        assert_eq!(decode(registers(0x01), 0).hour, 1);
+        assert_eq!(decode(registers(0x12 | PM), 0).hour, 12);
+        assert_eq!(decode(registers(0x11 | PM), 0).hour, 23);
+    }
+
+    #[test]
+    fn test_decode_binary() {
+        let registers = Registers {
+            second: 45,
+            minute: 30,
+            hour: 7 | PM,
+            day: 1,
+            month: 12,
+            year: 99,
+            century: 0,
+        };
+        let reading = decode(registers, BINARY_MODE);
+        assert_eq!(reading.year, 2099);
+        assert_eq!(reading.month, 12);
+        assert_eq!(reading.hour, 19);
+    }
+
+    #[test]
+    fn test_periodic_frequency() {
+        assert_eq!(periodic_frequency(6), 1024);
+        assert_eq!(periodic_frequency(15), 2);
+        assert_eq!(periodic_frequency(1), 8192);
+    }
+}
@@ -16,7 +16,7 @@
 
 use core::fmt::{Arguments, Result, Write};
 
-use super::instruction;
+use super::port::{self, ReadOnlyPort, WriteOnlyPort};
 
 pub enum Ports {
     COM1 = 0x3F8,
@@ -30,7 +30,12 @@ pub enum Ports {
 }
 
 pub struct Port {
-    address: u16,
+    data: port::Port<u8>,
+    interrupt_enable: port::Port<u8>,
+    fifo_control: WriteOnlyPort<u8>,
+    line_control: port::Port<u8>,
+    modem_control: port::Port<u8>,
+    line_status: ReadOnlyPort<u8>,
 }
 
 impl Write for Port {
@@ -63,47 +68,66 @@ impl Write for Port {
 
 impl Port {
     #[must_use]
-    pub fn new(port: Ports) -> Self {
+    pub const fn uninitialized(port: Ports) -> Self {
         let address = port as u16;
 
-        instruction::outb(address + 1, 0x00);
-        instruction::outb(address + 3, 0x80);
-        instruction::outb(address, 0x03);
-        instruction::outb(address + 1, 0x00);
-        instruction::outb(address + 3, 0x03);
-        instruction::outb(address + 2, 0xC7);
-        instruction::outb(address + 4, 0x0B);
-        instruction::outb(address + 4, 0x1E);
-        instruction::outb(address, 0xAE);
+        Self {
+            data: port::Port::new(address),
+            interrupt_enable: port::Port::new(address + 1),
+            fifo_control: WriteOnlyPort::new(address + 2),
+            line_control: port::Port::new(address + 3),
+            modem_control: port::Port::new(address + 4),
+            line_status: ReadOnlyPort::new(address + 5),
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the port fails its loopback test.
+    #[must_use]
+    pub fn new(port: Ports) -> Self {
+        let port = Self::uninitialized(port);
+
+        port.interrupt_enable.write(0x00);
+        port.line_control.write(0x80);
+        port.data.write(0x03);
+        port.interrupt_enable.write(0x00);
+        port.line_control.write(0x03);
+        port.fifo_control.write(0xC7);
+        port.modem_control.write(0x0B);
+        port.modem_control.write(0x1E);
+        port.data.write(0xAE);
 
         assert!(
-            instruction::inb(address) == 0xAE,
+            port.data.read() == 0xAE,
             "Failed to initialize serial port."
         );
 
-        instruction::outb(address + 4, 0x0F);
+        port.modem_control.write(0x0F);
+
+        port
+    }
 
-        Self { address }
+    pub fn enable_receive_interrupt(&self) {
+        self.interrupt_enable.write(0x01);
     }
 
     fn received(&self) -> bool {
-        (instruction::inb(self.address + 5) & 1) != 0
+        (self.line_status.read() & 1) != 0
     }
 
     fn transmit_empty(&self) -> bool {
-        (instruction::inb(self.address + 5) & 0x20) != 0
+        (self.line_status.read() & 0x20) != 0
     }
 
     #[must_use]
-    pub fn read(&self) -> u8 {
-        while !self.received() {}
-
-        instruction::inb(self.address)
+    pub fn try_read(&self) -> Option<u8> {
+        self.received().then(|| self.data.read())
     }
 
     pub fn write(&self, character: char) {
         while !self.transmit_empty() {}
 
-        instruction::outb(self.address, character as u8);
+        self.data.write(character as u8);
     }
 }
@@ -0,0 +1,210 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::arch::global_asm;
+use core::mem;
+use core::ptr;
+use core::sync::atomic::AtomicPtr;
+use core::sync::atomic::Ordering::{Acquire, Release};
+
+pub const STUB_SIZE: u64 = 16;
+
+pub type Handler = fn(&mut TrapFrame);
+
+static HANDLER: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(C)]
+pub struct TrapFrame {
+    pub r15: u64,
+    pub r14: u64,
+    pub r13: u64,
+    pub r12: u64,
+    pub r11: u64,
+    pub r10: u64,
+    pub r9: u64,
+    pub r8: u64,
+    pub rbp: u64,
+    pub rdi: u64,
+    pub rsi: u64,
+    pub rdx: u64,
+    pub rcx: u64,
+    pub rbx: u64,
+    pub rax: u64,
+    pub vector: u64,
+    pub error_code: u64,
+    pub instruction_pointer: u64,
+    pub code_segment: u64,
+    pub cpu_flags: u64,
+    pub stack_pointer: u64,
+    pub stack_segment: u64,
+}
+
+impl TrapFrame {
+    #[must_use]
+    pub fn is_user(&self) -> bool {
+        self.code_segment & 0x3 == 0x3
+    }
+}
+
+unsafe extern "C" {
+    static interrupt_stubs: u8;
+}
+
+global_asm!(
+    r#"
+    .altmacro
+    .macro interrupt_stub vector
+        .align 16
+        .if (\vector == 8) | (\vector == 10) | (\vector == 11) | (\vector == 12) | (\vector == 13) | (\vector == 14) | (\vector == 17) | (\vector == 21) | (\vector == 29) | (\vector == 30)
+        .else
+            push 0
+        .endif
+        push \vector
+        jmp interrupt_common
+    .endm
+
+    .section .text.interrupt_stubs, "ax"
+    .global interrupt_stubs
+    .align 16
+    interrupt_stubs:
+    .set vector, 0
+    .rept 256
+        interrupt_stub %vector
+        .set vector, vector + 1
+    .endr
+
+    interrupt_common:
+        cld
+        test qword ptr [rsp + 24], 3
+        jz 2f
+        swapgs
+    2:
+        push rax
+        push rbx
+        push rcx
+        push rdx
+        push rsi
+        push rdi
+        push rbp
+        push r8
+        push r9
+        push r10
+        push r11
+        push r12
+        push r13
+        push r14
+        push r15
+        mov rdi, rsp
+        call {dispatch}
+        pop r15
+        pop r14
+        pop r13
+        pop r12
+        pop r11
+        pop r10
+        pop r9
+        pop r8
+        pop rbp
+        pop rdi
+        pop rsi
+        pop rdx
+        pop rcx
+        pop rbx
+        pop rax
+        add rsp, 16
+        test qword ptr [rsp + 8], 3
+        jz 3f
+        swapgs
+    3:
+        iretq
+    .noaltmacro
+    "#,
+    dispatch = sym dispatch,
+);
+
+extern "C" fn dispatch(frame: &mut TrapFrame) {
+    let handler = HANDLER.load(Acquire);
+
+    if !handler.is_null() {
+        let handler = unsafe { mem::transmute::<*mut (), Handler>(handler) };
+        handler(frame);
+    }
+}
+
+pub fn set_handler(handler: Handler) {
+    HANDLER.store(handler as *mut (), Release);
+}
+
+#[must_use]
+pub fn stub(vector: u8) -> u64 {
+    (&raw const interrupt_stubs) as u64 + u64::from(vector) * STUB_SIZE
+}
+
+#[must_use]
+pub fn has_error_code(vector: u8) -> bool {
+    matches!(vector, 8 | 10..=14 | 17 | 21 | 29 | 30)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trap_frame_size() {
+        assert_eq!(size_of::<TrapFrame>(), 176);
+    }
+
+    #[test]
+    fn test_is_user() {
+        let mut frame = TrapFrame {
+            code_segment: 0x08,
+            ..TrapFrame::default()
+        };
+        assert!(!frame.is_user());
+
+        frame.code_segment = 0x1B;
+        assert!(frame.is_user());
+    }
+
+    #[test]
+    fn test_stub_address() {
+        assert_eq!(stub(1) - stub(0), STUB_SIZE);
+        assert_eq!(stub(255) - stub(0), 255 * STUB_SIZE);
+    }
+
+    #[test]
+    fn test_stub_error_code() {
+        for vector in 0..128 {
+            let code = unsafe { *(stub(vector) as *const [u8; 2]) };
+
+            if has_error_code(vector) {
+                assert_eq!(code, [0x6A, vector]);
+            } else {
+                assert_eq!(code, [0x6A, 0x00]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_has_error_code() {
+        assert!(!has_error_code(0));
+        assert!(has_error_code(8));
+        assert!(!has_error_code(9));
+        assert!(has_error_code(14));
+        assert!(!has_error_code(32));
+    }
+}
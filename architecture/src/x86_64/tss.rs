@@ -17,6 +17,9 @@
 use super::gdt::Selector;
 use super::instruction;
 
+pub const INTERRUPT_STACKS: u8 = 7;
+pub const PRIVILEGE_STACKS: u8 = 3;
+
 #[repr(C, packed(4))]
 pub struct Segment {
     reserved_1: u32,
@@ -35,6 +38,9 @@ impl Default for Segment {
 }
 
 impl Segment {
+    /// # Panics
+    ///
+    /// Panics if the segment size does not fit in the I/O map base.
     #[must_use]
     pub fn new() -> Self {
         Self {
@@ -48,6 +54,58 @@ impl Segment {
         }
     }
 
+    /// # Panics
+    ///
+    /// Panics if `index` is not between 1 and 7.
+    #[must_use]
+    pub fn interrupt_stack(&self, index: u8) -> u64 {
+        assert!(
+            (1..=INTERRUPT_STACKS).contains(&index),
+            "Failed to validate interrupt stack index."
+        );
+        let table = self.interrupt_stack_table;
+        table[usize::from(index - 1)]
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `index` is not between 1 and 7.
+    pub fn set_interrupt_stack(&mut self, index: u8, top: u64) {
+        assert!(
+            (1..=INTERRUPT_STACKS).contains(&index),
+            "Failed to validate interrupt stack index."
+        );
+        let mut table = self.interrupt_stack_table;
+        table[usize::from(index - 1)] = top;
+        self.interrupt_stack_table = table;
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `level` is not below 3.
+    #[must_use]
+    pub fn privilege_stack(&self, level: u8) -> u64 {
+        assert!(
+            level < PRIVILEGE_STACKS,
+            "Failed to validate privilege level."
+        );
+        let table = self.privilege_stack_table;
+        table[usize::from(level)]
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `level` is not below 3.
+    pub fn set_privilege_stack(&mut self, level: u8, top: u64) {
+        assert!(
+            level < PRIVILEGE_STACKS,
+            "Failed to validate privilege level."
+        );
+        let mut table = self.privilege_stack_table;
+        table[usize::from(level)] = top;
+        self.privilege_stack_table = table;
+    }
+
     pub fn load(selector: Selector) {
         instruction::ltr(selector);
     }
@@ -62,4 +120,32 @@ mod tests {
         let segment = Segment::new();
         assert_eq!(segment.io_map_base, 0x0068);
     }
+
+    #[test]
+    fn test_interrupt_stack() {
+        let mut segment = Segment::new();
+        segment.set_interrupt_stack(1, 0x1000);
+        segment.set_interrupt_stack(7, 0x7000);
+        assert_eq!(segment.interrupt_stack(1), 0x1000);
+        assert_eq!(segment.interrupt_stack(7), 0x7000);
+        assert_eq!(segment.interrupt_stack(2), 0);
+
+        let table = segment.interrupt_stack_table;
+        assert_eq!(table[0], 0x1000);
+        assert_eq!(table[6], 0x7000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to validate interrupt stack index.")]
+    fn test_interrupt_stack_zero() {
+        Segment::new().set_interrupt_stack(0, 0x1000);
+    }
+
+    #[test]
+    fn test_privilege_stack() {
+        let mut segment = Segment::new();
+        segment.set_privilege_stack(0, 0x2000);
+        assert_eq!(segment.privilege_stack(0), 0x2000);
+        assert_eq!(segment.privilege_stack(2), 0);
+    }
 }
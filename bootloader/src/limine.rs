@@ -15,6 +15,10 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 pub mod framebuffer;
+pub mod hhdm;
 pub mod info;
 pub mod marker;
+pub mod memmap;
+pub mod mp;
 pub mod revision;
+pub mod rsdp;
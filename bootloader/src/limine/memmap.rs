@@ -0,0 +1,138 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::{ptr, slice};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Usable,
+    Reserved,
+    AcpiReclaimable,
+    AcpiNvs,
+    BadMemory,
+    BootloaderReclaimable,
+    ExecutableAndModules,
+    Framebuffer,
+}
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct Entry {
+    base: u64,
+    length: u64,
+    kind: u64,
+}
+
+impl Entry {
+    #[must_use]
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    #[must_use]
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> Kind {
+        match self.kind {
+            0 => Kind::Usable,
+            2 => Kind::AcpiReclaimable,
+            3 => Kind::AcpiNvs,
+            4 => Kind::BadMemory,
+            5 => Kind::BootloaderReclaimable,
+            6 => Kind::ExecutableAndModules,
+            7 => Kind::Framebuffer,
+            _ => Kind::Reserved,
+        }
+    }
+}
+
+#[repr(C)]
+pub struct Request {
+    id: [u64; 4],
+    revision: u64,
+    response: *const Response,
+}
+
+impl Default for Request {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Request {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            id: [
+                0xc7b1_dd30_df4c_8b88,
+                0x0a82_e883_a194_f07b,
+                0x67cf_3d9d_378a_806f,
+                0xe304_acdf_c50c_3c62,
+            ],
+            revision: 0,
+            response: ptr::null(),
+        }
+    }
+
+    #[must_use]
+    pub fn response(&self) -> Option<Response> {
+        if self.response.is_null() {
+            None
+        } else {
+            unsafe {
+                let response = self.response.read_volatile();
+                Some(response)
+            }
+        }
+    }
+}
+
+unsafe impl Send for Request {}
+unsafe impl Sync for Request {}
+
+#[repr(C)]
+pub struct Response {
+    revision: u64,
+    entry_count: u64,
+    entries: *const *const Entry,
+}
+
+impl Response {
+    #[must_use]
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    #[must_use]
+    pub fn entry_count(&self) -> u64 {
+        self.entry_count
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the entry count does not fit in a `usize`.
+    pub fn entries(&self) -> impl Iterator<Item = Entry> {
+        let length = usize::try_from(self.entry_count).unwrap();
+        unsafe {
+            slice::from_raw_parts(self.entries, length)
+                .iter()
+                .map(|&x| x.read_volatile())
+        }
+    }
+}
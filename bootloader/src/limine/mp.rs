@@ -0,0 +1,141 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::sync::atomic::AtomicU64;
+use core::sync::atomic::Ordering::SeqCst;
+use core::{ptr, slice};
+
+pub type Entry = extern "C" fn(&'static Cpu) -> !;
+
+#[repr(C)]
+pub struct Cpu {
+    processor_id: u32,
+    lapic_id: u32,
+    reserved: u64,
+    goto_address: AtomicU64,
+    extra_argument: AtomicU64,
+}
+
+impl Cpu {
+    #[must_use]
+    pub fn processor_id(&self) -> u32 {
+        self.processor_id
+    }
+
+    #[must_use]
+    pub fn lapic_id(&self) -> u32 {
+        self.lapic_id
+    }
+
+    #[must_use]
+    pub fn extra_argument(&self) -> u64 {
+        self.extra_argument.load(SeqCst)
+    }
+
+    pub fn start(&self, entry: Entry, argument: u64) {
+        self.extra_argument.store(argument, SeqCst);
+        self.goto_address.store(entry as usize as u64, SeqCst);
+    }
+}
+
+#[repr(C)]
+pub struct Request {
+    id: [u64; 4],
+    revision: u64,
+    response: *const Response,
+    flags: u64,
+}
+
+impl Default for Request {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Request {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            id: [
+                0xc7b1_dd30_df4c_8b88,
+                0x0a82_e883_a194_f07b,
+                0x95a6_7b81_9a1b_857e,
+                0xa0b6_1b72_3b6a_73e0,
+            ],
+            revision: 0,
+            response: ptr::null(),
+            flags: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn response(&self) -> Option<Response> {
+        if self.response.is_null() {
+            None
+        } else {
+            unsafe {
+                let response = self.response.read_volatile();
+                Some(response)
+            }
+        }
+    }
+}
+
+unsafe impl Send for Request {}
+unsafe impl Sync for Request {}
+
+#[repr(C)]
+pub struct Response {
+    revision: u64,
+    flags: u32,
+    bsp_lapic_id: u32,
+    cpu_count: u64,
+    cpus: *const *const Cpu,
+}
+
+impl Response {
+    #[must_use]
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    #[must_use]
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    #[must_use]
+    pub fn bsp_lapic_id(&self) -> u32 {
+        self.bsp_lapic_id
+    }
+
+    #[must_use]
+    pub fn cpu_count(&self) -> u64 {
+        self.cpu_count
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the processor count does not fit in a `usize`.
+    pub fn cpus(&self) -> impl Iterator<Item = &'static Cpu> {
+        let length = usize::try_from(self.cpu_count).unwrap();
+        unsafe {
+            slice::from_raw_parts(self.cpus, length)
+                .iter()
+                .map(|&x| &*x)
+        }
+    }
+}
@@ -0,0 +1,232 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use bootloader::limine::rsdp;
+use core::ptr;
+use core::slice;
+use core::sync::atomic::Ordering::Relaxed;
+use core::sync::atomic::{AtomicBool, AtomicU64};
+use utility::info;
+
+use crate::memory;
+
+#[used]
+#[unsafe(link_section = ".limine_requests")]
+static RSDP_REQUEST: rsdp::Request = rsdp::Request::new();
+
+static ROOT: AtomicU64 = AtomicU64::new(0);
+static EXTENDED: AtomicBool = AtomicBool::new(false);
+
+#[repr(C, packed)]
+struct Rsdp {
+    signature: [u8; 8],
+    checksum: u8,
+    oem_id: [u8; 6],
+    revision: u8,
+    rsdt_address: u32,
+    length: u32,
+    xsdt_address: u64,
+    extended_checksum: u8,
+    reserved: [u8; 3],
+}
+
+#[derive(Clone, Copy)]
+#[repr(C, packed)]
+pub struct Header {
+    signature: [u8; 4],
+    length: u32,
+    revision: u8,
+    checksum: u8,
+    oem_id: [u8; 6],
+    oem_table_id: [u8; 8],
+    oem_revision: u32,
+    creator_id: u32,
+    creator_revision: u32,
+}
+
+#[derive(Clone, Copy)]
+pub struct Table {
+    address: u64,
+    header: Header,
+}
+
+impl Table {
+    fn new(physical: u64) -> Self {
+        let address = memory::map_physical(physical, size_of::<Header>() as u64, 0);
+        let header = unsafe { ptr::read_unaligned(address as *const Header) };
+        memory::map_physical(physical, u64::from(header.length), 0);
+
+        Self { address, header }
+    }
+
+    fn bytes(&self) -> &'static [u8] {
+        let length = usize::try_from(self.header.length).unwrap();
+        unsafe { slice::from_raw_parts(self.address as *const u8, length) }
+    }
+
+    pub fn data(&self) -> &'static [u8] {
+        &self.bytes()[size_of::<Header>()..]
+    }
+
+    fn is_valid(&self) -> bool {
+        self.bytes()
+            .iter()
+            .fold(0u8, |sum, &byte| sum.wrapping_add(byte))
+            == 0
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([data[offset], data[offset + 1]])
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+pub fn find(signature: [u8; 4]) -> Option<Table> {
+    let root = Table::new(ROOT.load(Relaxed));
+    let data = root.data();
+    let extended = EXTENDED.load(Relaxed);
+    let size = if extended { 8 } else { 4 };
+
+    (0..data.len() / size)
+        .map(|index| {
+            if extended {
+                read_u64(data, index * size)
+            } else {
+                u64::from(read_u32(data, index * size))
+            }
+        })
+        .map(Table::new)
+        .find(|table| table.header.signature == signature && table.is_valid())
+}
+
+pub enum MadtEntry {
+    IoApic {
+        address: u32,
+        interrupt_base: u32,
+    },
+    InterruptSourceOverride {
+        bus: u8,
+        source: u8,
+        interrupt: u32,
+        flags: u16,
+    },
+    Other,
+}
+
+pub struct Madt {
+    entries: &'static [u8],
+}
+
+impl Madt {
+    pub fn entries(&self) -> MadtEntries {
+        MadtEntries {
+            data: self.entries,
+            offset: 0,
+        }
+    }
+}
+
+pub struct MadtEntries {
+    data: &'static [u8],
+    offset: usize,
+}
+
+impl Iterator for MadtEntries {
+    type Item = MadtEntry;
+
+    fn next(&mut self) -> Option<MadtEntry> {
+        if self.offset + 2 > self.data.len() {
+            return None;
+        }
+
+        let kind = self.data[self.offset];
+        let length = usize::from(self.data[self.offset + 1]);
+
+        if length < 2 || self.offset + length > self.data.len() {
+            return None;
+        }
+
+        let entry = &self.data[self.offset..self.offset + length];
+        self.offset += length;
+
+        let entry = match kind {
+            1 => MadtEntry::IoApic {
+                address: read_u32(entry, 4),
+                interrupt_base: read_u32(entry, 8),
+            },
+            2 => MadtEntry::InterruptSourceOverride {
+                bus: entry[2],
+                source: entry[3],
+                interrupt: read_u32(entry, 4),
+                flags: read_u16(entry, 8),
+            },
+            _ => MadtEntry::Other,
+        };
+
+        Some(entry)
+    }
+}
+
+pub fn madt() -> Option<Madt> {
+    let table = find(*b"APIC")?;
+    let data = table.data();
+
+    Some(Madt {
+        entries: &data[8..],
+    })
+}
+
+pub fn century() -> Option<u8> {
+    let table = find(*b"FACP")?;
+    let data = table.data();
+
+    data.get(72).copied().filter(|&register| register != 0)
+}
+
+pub fn hpet() -> Option<u64> {
+    let table = find(*b"HPET")?;
+    let data = table.data();
+
+    match data[4] {
+        0 => Some(read_u64(data, 8)),
+        _ => None,
+    }
+}
+
+pub fn init() {
+    let response = RSDP_REQUEST.response().unwrap();
+    assert_eq!(response.revision(), 0);
+
+    let address = memory::map_physical(response.address(), size_of::<Rsdp>() as u64, 0);
+    let rsdp = unsafe { ptr::read_unaligned(address as *const Rsdp) };
+    assert_eq!(&rsdp.signature, b"RSD PTR ");
+
+    if rsdp.revision >= 2 && rsdp.xsdt_address != 0 {
+        ROOT.store(rsdp.xsdt_address, Relaxed);
+        EXTENDED.store(true, Relaxed);
+    } else {
+        ROOT.store(u64::from(rsdp.rsdt_address), Relaxed);
+    }
+
+    info!("Initialized the ACPI table parser.");
+}
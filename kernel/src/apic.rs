@@ -0,0 +1,172 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use architecture::x86_64::apic::{Divide, LocalApic, Mode, TimerMode};
+use architecture::x86_64::cpuid;
+use architecture::x86_64::idt::{Action, Options, Status};
+use architecture::x86_64::paging::PAGE_SIZE;
+use architecture::x86_64::trap::TrapFrame;
+use core::sync::atomic::Ordering::Relaxed;
+use core::sync::atomic::{AtomicBool, AtomicU64};
+use utility::time::NANOSECONDS_PER_SECOND;
+use utility::{info, warn};
+
+use crate::interrupt;
+use crate::memory;
+use crate::pic::PIC;
+use crate::pit;
+use crate::tick;
+use crate::workqueue::{self, Work};
+
+pub const TIMER_VECTOR: u8 = 0xFD;
+pub const ERROR_VECTOR: u8 = 0xFE;
+pub const SPURIOUS_VECTOR: u8 = 0xFF;
+
+static X2APIC: AtomicBool = AtomicBool::new(false);
+static BASE: AtomicU64 = AtomicU64::new(0);
+static TIMER_FREQUENCY: AtomicU64 = AtomicU64::new(0);
+
+pub fn local() -> LocalApic {
+    if X2APIC.load(Relaxed) {
+        LocalApic::new(Mode::X2Apic)
+    } else {
+        LocalApic::new(Mode::XApic {
+            base: BASE.load(Relaxed),
+        })
+    }
+}
+
+fn timer_handler(_frame: &mut TrapFrame) -> Status {
+    local().end_of_interrupt();
+    tick::event();
+
+    Status::Handled
+}
+
+fn error_handler(_frame: &mut TrapFrame) -> Status {
+    let apic = local();
+    let status = apic.error_status();
+
+    let _ = workqueue::SYSTEM.queue(Work::new(report_error, status as usize));
+
+    apic.end_of_interrupt();
+
+    Status::Handled
+}
+
+fn report_error(status: usize) {
+    warn!("Handled a local APIC error ({status:#x}).");
+}
+
+fn spurious_handler(_frame: &mut TrapFrame) -> Status {
+    Status::Handled
+}
+
+pub struct ClockEvent;
+
+impl tick::Device for ClockEvent {
+    fn name(&self) -> &'static str {
+        "local APIC timer"
+    }
+
+    fn rating(&self) -> Option<u16> {
+        match TIMER_FREQUENCY.load(Relaxed) {
+            0 => None,
+            _ if cpuid::has_always_running_timer() => Some(300),
+            _ => Some(150),
+        }
+    }
+
+    fn is_one_shot_capable(&self) -> bool {
+        true
+    }
+
+    fn enable(&self) {
+        local().unmask_timer();
+    }
+
+    fn set_periodic(&self, frequency: u32) {
+        let count = TIMER_FREQUENCY.load(Relaxed) / u64::from(frequency.max(1));
+        let apic = local();
+
+        apic.set_timer(TIMER_VECTOR, TimerMode::Periodic, Divide::By16);
+        apic.set_initial_count(u32::try_from(count.max(1)).unwrap_or(u32::MAX));
+    }
+
+    fn set_one_shot(&self, nanoseconds: u64) {
+        let count = u128::from(nanoseconds) * u128::from(TIMER_FREQUENCY.load(Relaxed))
+            / u128::from(NANOSECONDS_PER_SECOND);
+        let apic = local();
+
+        apic.set_timer(TIMER_VECTOR, TimerMode::OneShot, Divide::By16);
+        apic.set_initial_count(u32::try_from(count.max(1)).unwrap_or(u32::MAX));
+    }
+}
+
+pub fn init_secondary() {
+    let apic = local();
+    apic.enable(SPURIOUS_VECTOR);
+    apic.mask_local_interrupts();
+    apic.set_error_vector(ERROR_VECTOR);
+    apic.mask_timer();
+    apic.end_of_interrupt();
+}
+
+pub fn init() {
+    assert!(cpuid::has_apic(), "Failed to find a local APIC.");
+
+    if cpuid::has_x2apic() {
+        X2APIC.store(true, Relaxed);
+    } else {
+        let base = memory::map_mmio(LocalApic::physical_base(), PAGE_SIZE);
+        BASE.store(base, Relaxed);
+    }
+
+    for (vector, action) in [
+        (TIMER_VECTOR, timer_handler as Action),
+        (ERROR_VECTOR, error_handler),
+        (SPURIOUS_VECTOR, spurious_handler),
+    ] {
+        interrupt::register(vector, Options::new(), action)
+            .expect("Failed to register a local APIC handler.");
+    }
+
+    let apic = local();
+    apic.enable(SPURIOUS_VECTOR);
+    apic.mask_local_interrupts();
+    apic.set_error_vector(ERROR_VECTOR);
+    apic.set_timer(TIMER_VECTOR, TimerMode::OneShot, Divide::By16);
+    apic.mask_timer();
+
+    apic.set_initial_count(u32::MAX);
+    let frequency = pit::calibrate(|| u64::from(u32::MAX - apic.current_count()));
+    TIMER_FREQUENCY.store(frequency, Relaxed);
+    apic.stop_timer();
+
+    PIC.lock().disable();
+
+    apic.end_of_interrupt();
+
+    let mode = match apic.mode() {
+        Mode::XApic { .. } => "xAPIC",
+        Mode::X2Apic => "x2APIC",
+    };
+
+    info!(
+        "Initialized the local APIC in {mode} mode with a {} kHz timer.",
+        frequency / 1_000
+    );
+}
@@ -0,0 +1,76 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::sync::atomic::Ordering::Relaxed;
+use core::sync::atomic::{AtomicU64, AtomicUsize};
+use utility::info;
+use utility::time::NANOSECONDS_PER_SECOND;
+
+use crate::{hpet, tick, tsc};
+
+const SHIFT: u32 = 32;
+
+pub trait Source: Sync {
+    fn name(&self) -> &'static str;
+
+    fn rating(&self) -> Option<u16>;
+
+    fn frequency(&self) -> u64;
+
+    fn read(&self) -> u64;
+}
+
+static SOURCES: [&dyn Source; 3] = [&tsc::ClockSource, &hpet::ClockSource, &tick::Jiffies];
+
+static SOURCE: AtomicUsize = AtomicUsize::new(0);
+static MULTIPLIER: AtomicU64 = AtomicU64::new(0);
+static BASE: AtomicU64 = AtomicU64::new(0);
+
+pub fn nanoseconds() -> u64 {
+    let source = SOURCES[SOURCE.load(Relaxed)];
+    let cycles = u128::from(source.read().wrapping_sub(BASE.load(Relaxed)));
+    let multiplier = u128::from(MULTIPLIER.load(Relaxed));
+
+    u64::try_from((cycles * multiplier) >> SHIFT).unwrap_or(u64::MAX)
+}
+
+pub fn is_tick_based() -> bool {
+    SOURCES[SOURCE.load(Relaxed)].name() == tick::Jiffies.name()
+}
+
+pub fn init() {
+    let (index, source, rating) = SOURCES
+        .iter()
+        .enumerate()
+        .filter_map(|(index, source)| Some((index, *source, source.rating()?)))
+        .max_by_key(|&(_, _, rating)| rating)
+        .expect("Failed to find a clock source.");
+
+    let multiplier =
+        (u128::from(NANOSECONDS_PER_SECOND) << SHIFT) / u128::from(source.frequency().max(1));
+
+    SOURCE.store(index, Relaxed);
+    BASE.store(source.read(), Relaxed);
+    MULTIPLIER.store(
+        u64::try_from(multiplier).expect("Failed to scale the clock frequency."),
+        Relaxed,
+    );
+
+    info!(
+        "Initialized the monotonic clock using the {} (rating {rating}).",
+        source.name()
+    );
+}
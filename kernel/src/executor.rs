@@ -0,0 +1,115 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::future::{self, Future};
+use core::sync::atomic::Ordering::{AcqRel, Acquire, Release};
+use core::sync::atomic::{AtomicBool, AtomicUsize};
+use core::task::{Poll, Waker};
+use utility::executor::{Error, Executor, Handle};
+use utility::info;
+use utility::lock::Spinlock;
+
+use crate::interrupt;
+use crate::thread::{self, JoinHandle};
+
+pub use utility::executor::yield_now;
+
+const CAPACITY: usize = 32;
+
+const SIZE: usize = 512;
+
+const NONE: usize = usize::MAX;
+
+static EXECUTOR: Executor<CAPACITY, SIZE> = Executor::new(notify);
+
+static RUNNER: AtomicUsize = AtomicUsize::new(NONE);
+
+pub struct Event {
+    pending: AtomicBool,
+    waker: Spinlock<Option<Waker>>,
+}
+
+impl Event {
+    pub const fn new() -> Self {
+        Self {
+            pending: AtomicBool::new(false),
+            waker: Spinlock::new(None),
+        }
+    }
+
+    pub fn signal(&self) {
+        self.pending.store(true, Release);
+
+        if let Some(waker) = interrupt::without_interrupts(|| self.waker.lock().take()) {
+            waker.wake();
+        }
+    }
+
+    pub async fn wait(&self) {
+        future::poll_fn(|context| {
+            if self.pending.swap(false, AcqRel) {
+                return Poll::Ready(());
+            }
+
+            interrupt::without_interrupts(|| *self.waker.lock() = Some(context.waker().clone()));
+
+            if self.pending.swap(false, AcqRel) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+    }
+}
+
+fn notify() {
+    let runner = RUNNER.load(Acquire);
+
+    if runner != NONE {
+        thread::wake(runner);
+    }
+}
+
+fn run(_: usize) -> usize {
+    loop {
+        EXECUTOR.run();
+
+        if EXECUTOR.is_idle() {
+            thread::sleep();
+        } else {
+            thread::yield_now();
+        }
+    }
+}
+
+pub fn spawn<F>(future: F) -> Result<Handle, Error>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    EXECUTOR.spawn(future)
+}
+
+pub fn init() -> JoinHandle {
+    let runner = thread::spawn("executor", run, 0).expect("Failed to spawn the executor thread.");
+    runner.set_nice(-10);
+    runner.set_affinity(1);
+    RUNNER.store(runner.id(), Release);
+
+    info!("Initialized the task executor.");
+
+    runner
+}
@@ -14,18 +14,31 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use architecture::x86_64::gdt::Table;
-use core::cell::LazyCell;
-use utility::info;
-use utility::lock::Spinlock;
+use architecture::x86_64::gdt::{Selector, Table};
+use utility::lock::RwSpinlock;
+use utility::{debug, info};
 
-use crate::tss::TSS;
+use crate::smp::MAX_CPUS;
+use crate::tss;
 
-pub static GDT: Spinlock<LazyCell<Table>> =
-    Spinlock::new(LazyCell::new(|| Table::new(&**TSS.lock())));
+static GDT: [RwSpinlock<Table>; MAX_CPUS] = [const { RwSpinlock::new(Table::empty()) }; MAX_CPUS];
+
+pub fn selector(index: usize) -> Selector {
+    GDT[0].read().selector(index)
+}
+
+pub fn load(cpu: usize) {
+    let mut table = GDT[cpu].write();
+    *table = Table::new(tss::segment(cpu));
+    table.load();
+}
 
 pub fn init() {
-    GDT.lock().load();
+    load(0);
+
+    for (selector, entry) in GDT[0].read().entries() {
+        debug!("GDT {:#06x}: {entry}", selector.0);
+    }
 
     info!("Initialized the global descriptor table.");
 }
@@ -0,0 +1,145 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use architecture::x86_64::hpet::{Hpet, Route};
+use architecture::x86_64::idt::{Options, Status};
+use architecture::x86_64::trap::TrapFrame;
+use core::sync::atomic::AtomicU64;
+use core::sync::atomic::Ordering::Relaxed;
+use utility::time::NANOSECONDS_PER_SECOND;
+use utility::{info, warn};
+
+use crate::{acpi, apic, clock, interrupt, memory, tick};
+
+pub const VECTOR: u8 = 0xFC;
+
+const COMPARATOR: u8 = 0;
+
+static BASE: AtomicU64 = AtomicU64::new(0);
+static FREQUENCY: AtomicU64 = AtomicU64::new(0);
+
+pub fn get() -> Option<Hpet> {
+    match BASE.load(Relaxed) {
+        0 => None,
+        base => Some(Hpet::new(base)),
+    }
+}
+
+fn route() -> Route {
+    let destination = u8::try_from(apic::local().id()).expect("Failed to route to the local APIC.");
+
+    Route::message(destination, VECTOR)
+}
+
+fn ticks(nanoseconds: u64) -> u64 {
+    let ticks = u128::from(nanoseconds) * u128::from(FREQUENCY.load(Relaxed))
+        / u128::from(NANOSECONDS_PER_SECOND);
+
+    u64::try_from(ticks).unwrap_or(u64::MAX).max(1)
+}
+
+fn timer_handler(_frame: &mut TrapFrame) -> Status {
+    apic::local().end_of_interrupt();
+    tick::event();
+
+    Status::Handled
+}
+
+pub struct ClockSource;
+
+impl clock::Source for ClockSource {
+    fn name(&self) -> &'static str {
+        "HPET"
+    }
+
+    fn rating(&self) -> Option<u16> {
+        get().filter(Hpet::is_64_bit).map(|_| 250)
+    }
+
+    fn frequency(&self) -> u64 {
+        FREQUENCY.load(Relaxed)
+    }
+
+    fn read(&self) -> u64 {
+        get().map_or(0, |hpet| hpet.counter())
+    }
+}
+
+pub struct ClockEvent;
+
+impl tick::Device for ClockEvent {
+    fn name(&self) -> &'static str {
+        "HPET"
+    }
+
+    fn rating(&self) -> Option<u16> {
+        get()
+            .filter(|hpet| hpet.is_fsb_capable(COMPARATOR))
+            .map(|_| 200)
+    }
+
+    fn is_one_shot_capable(&self) -> bool {
+        true
+    }
+
+    fn enable(&self) {
+        interrupt::register(VECTOR, Options::new(), timer_handler)
+            .expect("Failed to register the HPET handler.");
+    }
+
+    fn set_periodic(&self, frequency: u32) {
+        if let Some(hpet) = get() {
+            let period = FREQUENCY.load(Relaxed) / u64::from(frequency.max(1));
+            hpet.set_periodic(COMPARATOR, period, route());
+        }
+    }
+
+    fn set_one_shot(&self, nanoseconds: u64) {
+        if let Some(hpet) = get() {
+            let deadline = hpet.counter().wrapping_add(ticks(nanoseconds));
+            hpet.set_one_shot(COMPARATOR, deadline, route());
+        }
+    }
+}
+
+pub fn init() {
+    let Some(physical) = acpi::hpet() else {
+        warn!("Failed to find the high precision event timer.");
+        return;
+    };
+
+    let base = memory::map_mmio(physical, 0x400);
+    let hpet = Hpet::new(base);
+
+    for timer in 0..hpet.timer_count() {
+        hpet.stop(timer);
+    }
+
+    hpet.disable();
+    hpet.set_legacy_replacement(false);
+    hpet.set_counter(0);
+    hpet.enable();
+
+    FREQUENCY.store(hpet.frequency(), Relaxed);
+    BASE.store(base, Relaxed);
+
+    info!(
+        "Initialized the high precision event timer at {} kHz with {} {}-bit comparators.",
+        hpet.frequency() / 1_000,
+        hpet.timer_count(),
+        if hpet.is_64_bit() { 64 } else { 32 }
+    );
+}
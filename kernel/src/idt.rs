@@ -14,54 +14,39 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use architecture::x86_64::idt::{Handlers, Table};
-use core::cell::LazyCell;
+use architecture::x86_64::exception::Exception;
+use architecture::x86_64::idt::{Descriptor, Options, Table};
+use architecture::x86_64::trap;
 use utility::info;
-use utility::lock::Spinlock;
+use utility::lock::{Lazy, RwSpinlock};
 
-use crate::gdt::GDT;
-use crate::isr::{
-    alignment_check_handler, bound_range_exceeded_handler, breakpoint_handler,
-    control_protection_handler, debug_handler, device_not_available_handler,
-    division_error_handler, double_fault_handler, general_protection_fault_handler,
-    hypervisor_injection_handler, invalid_opcode_handler, invalid_tss_handler,
-    machine_check_handler, non_maskable_interrupt_handler, overflow_handler, page_fault_handler,
-    security_handler, segment_not_present_handler, simd_floating_point_handler,
-    stack_segment_fault_handler, virtualization_handler, vmm_communication_handler,
-    x87_floating_point_handler,
+use crate::gdt;
+use crate::tss::{
+    DEBUG_STACK, DOUBLE_FAULT_STACK, MACHINE_CHECK_STACK, NON_MASKABLE_INTERRUPT_STACK,
 };
 
-pub static IDT: Spinlock<LazyCell<Table>> = Spinlock::new(LazyCell::new(|| {
-    let handlers = Handlers {
-        division_error_handler,
-        debug_handler,
-        non_maskable_interrupt_handler,
-        breakpoint_handler,
-        overflow_handler,
-        bound_range_exceeded_handler,
-        invalid_opcode_handler,
-        device_not_available_handler,
-        double_fault_handler,
-        invalid_tss_handler,
-        segment_not_present_handler,
-        stack_segment_fault_handler,
-        general_protection_fault_handler,
-        page_fault_handler,
-        x87_floating_point_handler,
-        alignment_check_handler,
-        machine_check_handler,
-        simd_floating_point_handler,
-        virtualization_handler,
-        control_protection_handler,
-        hypervisor_injection_handler,
-        vmm_communication_handler,
-        security_handler,
-    };
+pub static IDT: Lazy<RwSpinlock<Table>> = Lazy::new(|| {
+    let selector = gdt::selector(1);
+    let mut table = Table::default();
 
-    Table::new(&handlers, GDT.lock().selector(1))
-}));
+    for vector in 0..=u8::MAX {
+        let options = match Exception::from_vector(vector) {
+            Some(Exception::Debug) => Options::new().with_stack_index(DEBUG_STACK),
+            Some(Exception::NonMaskableInterrupt) => {
+                Options::new().with_stack_index(NON_MASKABLE_INTERRUPT_STACK)
+            }
+            Some(Exception::DoubleFault) => Options::new().with_stack_index(DOUBLE_FAULT_STACK),
+            Some(Exception::MachineCheck) => Options::new().with_stack_index(MACHINE_CHECK_STACK),
+            _ => Options::new(),
+        };
+        let descriptor = Descriptor::with_address(trap::stub(vector), selector, options);
+        table.set(vector, descriptor);
+    }
+
+    RwSpinlock::new(table)
+});
 
 pub fn init() {
-    IDT.lock().load();
+    IDT.read().load();
     info!("Initialized the interrupt descriptor table.");
 }
@@ -0,0 +1,154 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use architecture::x86_64::exception::EXCEPTIONS;
+use architecture::x86_64::idt::{Action, Descriptor, Error, Handle, Options, Registry, Status};
+use architecture::x86_64::instruction;
+use architecture::x86_64::register::RFLAGS;
+use architecture::x86_64::trap::{self, TrapFrame};
+use core::sync::atomic::AtomicU64;
+use core::sync::atomic::Ordering::Relaxed;
+use utility::lock::{Interrupts, Spinlock, set_interrupts};
+use utility::{info, warn};
+
+use crate::gdt;
+use crate::idt::IDT;
+use crate::irq;
+use crate::isr;
+use crate::percpu::percpu;
+use crate::smp;
+use crate::softirq;
+use crate::thread;
+
+static REGISTRY: Spinlock<Registry> = Spinlock::new(Registry::new());
+
+static UNHANDLED: [AtomicU64; 256] = [const { AtomicU64::new(0) }; 256];
+
+static INTERRUPTS: Interrupts = Interrupts {
+    save,
+    restore,
+    cpu: smp::id,
+};
+
+fn save() -> u64 {
+    let flags = RFLAGS::get();
+    instruction::cli();
+
+    flags
+}
+
+fn restore(flags: u64) {
+    if flags & RFLAGS::INTERRUPT_FLAG != 0 {
+        instruction::sti();
+    }
+}
+
+pub fn without_interrupts<R>(function: impl FnOnce() -> R) -> R {
+    let enabled = RFLAGS::interrupts_enabled();
+    instruction::cli();
+
+    let result = function();
+
+    if enabled {
+        instruction::sti();
+    }
+
+    result
+}
+
+pub fn register(vector: u8, options: Options, action: Action) -> Result<Handle, Error> {
+    without_interrupts(|| {
+        let handle = REGISTRY.lock().register(vector, action)?;
+
+        let selector = gdt::selector(1);
+        IDT.write().set(
+            vector,
+            Descriptor::with_address(trap::stub(vector), selector, options),
+        );
+
+        Ok(handle)
+    })
+}
+
+pub fn unregister(handle: Handle) -> Result<(), Error> {
+    without_interrupts(|| REGISTRY.lock().unregister(handle))
+}
+
+pub fn replace(handle: Handle, action: Action) -> Result<(), Error> {
+    without_interrupts(|| REGISTRY.lock().replace(handle, action))
+}
+
+pub fn is_registered(vector: u8) -> bool {
+    without_interrupts(|| REGISTRY.lock().is_registered(vector))
+}
+
+pub fn is_active() -> bool {
+    percpu!(depth) != 0
+}
+
+fn dispatch(frame: &mut TrapFrame) {
+    percpu!(depth += 1);
+
+    handle(frame);
+
+    percpu!(depth -= 1);
+
+    if percpu!(depth) == 0 && frame.vector >= u64::from(EXCEPTIONS) {
+        if frame.cpu_flags & RFLAGS::INTERRUPT_FLAG != 0 {
+            softirq::run();
+        }
+
+        thread::preempt();
+    }
+}
+
+fn handle(frame: &mut TrapFrame) {
+    let vector = u8::try_from(frame.vector).expect("Failed to decode the interrupt vector.");
+
+    if vector < EXCEPTIONS {
+        isr::handle(frame);
+        return;
+    }
+
+    if irq::is_spurious(vector) {
+        return;
+    }
+
+    let chain = REGISTRY.lock().chain(vector);
+
+    if Registry::dispatch(&chain, frame) == Status::Unhandled {
+        let count = UNHANDLED[usize::from(vector)].fetch_add(1, Relaxed) + 1;
+
+        warn!(
+            "Unhandled interrupt vector {vector} (count {count}, error code {:#x}, RIP {:#x}, CS {:#x}, RFLAGS {:#x}, RSP {:#x}, SS {:#x}).",
+            frame.error_code,
+            frame.instruction_pointer,
+            frame.code_segment,
+            frame.cpu_flags,
+            frame.stack_pointer,
+            frame.stack_segment,
+        );
+    }
+
+    irq::end_of_interrupt(vector);
+}
+
+pub fn init() {
+    trap::set_handler(dispatch);
+    set_interrupts(&INTERRUPTS);
+
+    info!("Initialized the interrupt dispatcher.");
+}
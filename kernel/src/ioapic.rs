@@ -0,0 +1,153 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use architecture::x86_64::ioapic::{IoApic, Polarity, Redirection, Trigger};
+use architecture::x86_64::paging::PAGE_SIZE;
+use core::sync::atomic::AtomicBool;
+use core::sync::atomic::Ordering::Relaxed;
+use utility::info;
+use utility::lock::Spinlock;
+
+use crate::acpi::{self, MadtEntry};
+use crate::memory;
+
+const MAX_IO_APICS: usize = 8;
+const ISA_LINES: usize = 16;
+
+#[derive(Clone, Copy)]
+struct Override {
+    interrupt: u32,
+    polarity: Polarity,
+    trigger: Trigger,
+}
+
+static IO_APICS: Spinlock<[Option<IoApic>; MAX_IO_APICS]> = Spinlock::new([None; MAX_IO_APICS]);
+
+static OVERRIDES: Spinlock<[Option<Override>; ISA_LINES]> = Spinlock::new([None; ISA_LINES]);
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+fn polarity(flags: u16, default: Polarity) -> Polarity {
+    match flags & 0b11 {
+        0b01 => Polarity::High,
+        0b11 => Polarity::Low,
+        _ => default,
+    }
+}
+
+fn trigger(flags: u16, default: Trigger) -> Trigger {
+    match (flags >> 2) & 0b11 {
+        0b01 => Trigger::Edge,
+        0b11 => Trigger::Level,
+        _ => default,
+    }
+}
+
+fn find(interrupt: u32) -> Option<IoApic> {
+    IO_APICS
+        .lock()
+        .iter()
+        .flatten()
+        .find(|io_apic| io_apic.handles(interrupt))
+        .copied()
+}
+
+pub fn is_active() -> bool {
+    ACTIVE.load(Relaxed)
+}
+
+pub fn isa_interrupt(line: u8) -> (u32, Polarity, Trigger) {
+    match OVERRIDES.lock()[usize::from(line)] {
+        Some(entry) => (entry.interrupt, entry.polarity, entry.trigger),
+        None => (u32::from(line), Polarity::High, Trigger::Edge),
+    }
+}
+
+pub fn route(interrupt: u32, vector: u8, destination: u8, polarity: Polarity, trigger: Trigger) {
+    let io_apic = find(interrupt).expect("Failed to find an I/O APIC for the interrupt.");
+    let redirection = Redirection::new(vector, destination, polarity, trigger);
+
+    io_apic.set_redirection(interrupt, &redirection);
+}
+
+pub fn mask(interrupt: u32) {
+    let io_apic = find(interrupt).expect("Failed to find an I/O APIC for the interrupt.");
+
+    io_apic.mask(interrupt);
+}
+
+pub fn route_isa(line: u8, vector: u8, destination: u8) {
+    let (interrupt, polarity, trigger) = isa_interrupt(line);
+
+    route(interrupt, vector, destination, polarity, trigger);
+}
+
+pub fn route_pci(interrupt: u32, vector: u8, destination: u8) {
+    route(
+        interrupt,
+        vector,
+        destination,
+        Polarity::Low,
+        Trigger::Level,
+    );
+}
+
+pub fn init() {
+    let madt = acpi::madt().expect("Failed to find the MADT.");
+    let mut io_apics = IO_APICS.lock();
+    let mut overrides = OVERRIDES.lock();
+    let mut count = 0;
+
+    for entry in madt.entries() {
+        match entry {
+            MadtEntry::IoApic {
+                address,
+                interrupt_base,
+            } if count < MAX_IO_APICS => {
+                let base = memory::map_mmio(u64::from(address), PAGE_SIZE);
+                io_apics[count] = Some(IoApic::new(base, interrupt_base));
+                count += 1;
+            }
+            MadtEntry::InterruptSourceOverride {
+                bus: 0,
+                source,
+                interrupt,
+                flags,
+            } if usize::from(source) < ISA_LINES => {
+                overrides[usize::from(source)] = Some(Override {
+                    interrupt,
+                    polarity: polarity(flags, Polarity::High),
+                    trigger: trigger(flags, Trigger::Edge),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    assert!(count > 0, "Failed to find an I/O APIC.");
+
+    for io_apic in io_apics.iter().flatten() {
+        let base = io_apic.interrupt_base();
+
+        for interrupt in base..base + io_apic.entry_count() {
+            io_apic.mask(interrupt);
+        }
+    }
+
+    ACTIVE.store(true, Relaxed);
+
+    info!("Initialized the I/O APIC.");
+}
@@ -0,0 +1,112 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use architecture::x86_64::idt::{Action, Error, Handle, Options};
+use core::sync::atomic::AtomicU64;
+use core::sync::atomic::Ordering::Relaxed;
+
+use crate::apic;
+use crate::interrupt;
+use crate::ioapic;
+use crate::pic::{PIC, PRIMARY_OFFSET};
+
+pub const LINES: u8 = 24;
+
+static SPURIOUS: AtomicU64 = AtomicU64::new(0);
+
+pub fn vector(line: u8) -> u8 {
+    PRIMARY_OFFSET + line
+}
+
+fn line(vector: u8) -> Option<u8> {
+    vector
+        .checked_sub(PRIMARY_OFFSET)
+        .filter(|&line| line < LINES)
+}
+
+pub fn register(line: u8, action: Action) -> Result<Handle, Error> {
+    let handle = interrupt::register(vector(line), Options::new(), action)?;
+
+    interrupt::without_interrupts(|| {
+        if ioapic::is_active() {
+            let destination =
+                u8::try_from(apic::local().id()).expect("Failed to route to the local APIC.");
+
+            if line < 16 {
+                ioapic::route_isa(line, vector(line), destination);
+            } else {
+                ioapic::route_pci(u32::from(line), vector(line), destination);
+            }
+        } else {
+            PIC.lock().unmask(line);
+        }
+    });
+
+    Ok(handle)
+}
+
+pub fn unregister(handle: Handle) -> Result<(), Error> {
+    interrupt::unregister(handle)?;
+
+    let Some(line) = line(handle.vector()) else {
+        return Ok(());
+    };
+
+    interrupt::without_interrupts(|| {
+        if interrupt::is_registered(handle.vector()) {
+            return;
+        }
+
+        if ioapic::is_active() {
+            let interrupt = if line < 16 {
+                ioapic::isa_interrupt(line).0
+            } else {
+                u32::from(line)
+            };
+
+            ioapic::mask(interrupt);
+        } else {
+            PIC.lock().mask(line);
+        }
+    });
+
+    Ok(())
+}
+
+pub fn is_spurious(vector: u8) -> bool {
+    match line(vector) {
+        Some(line) if !ioapic::is_active() => {
+            let spurious = PIC.lock().is_spurious(line);
+
+            if spurious {
+                SPURIOUS.fetch_add(1, Relaxed);
+            }
+
+            spurious
+        }
+        _ => false,
+    }
+}
+
+pub fn end_of_interrupt(vector: u8) {
+    if let Some(line) = line(vector) {
+        if ioapic::is_active() {
+            apic::local().end_of_interrupt();
+        } else {
+            PIC.lock().end_of_interrupt(line);
+        }
+    }
+}
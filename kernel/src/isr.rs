@@ -14,101 +14,266 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use architecture::x86_64::idt::Frame;
-use utility::{debug, error, warn};
+use architecture::x86_64::decoder;
+use architecture::x86_64::exception::{Exception, PageFaultError, SelectorError};
+use architecture::x86_64::fixup::{self, Entry};
+use architecture::x86_64::register::{CR0, CR2, CR3, CR4};
+use architecture::x86_64::trap::{self, TrapFrame};
+use core::{fmt, ptr, slice};
+use utility::logging::Level;
+use utility::{debug, error};
 
-use crate::done;
+use crate::percpu::percpu;
+use crate::{logger, memory, thread};
 
-pub extern "x86-interrupt" fn division_error_handler(_frame: Frame) {
-    warn!("Handled the division error exception.");
-}
+const OPCODE_BYTES: usize = 16;
 
-pub extern "x86-interrupt" fn debug_handler(_frame: Frame) {
-    debug!("Handled the debug trap.");
+unsafe extern "C" {
+    static __exception_table_start: Entry;
+    static __exception_table_end: Entry;
 }
 
-pub extern "x86-interrupt" fn non_maskable_interrupt_handler(_frame: Frame) {
-    warn!("Handled the non-maskable interrupt exception.");
-}
+struct Bytes<'a>(&'a [u8]);
 
-pub extern "x86-interrupt" fn breakpoint_handler(_frame: Frame) {
-    debug!("Handled the breakpoint trap.");
-}
+impl fmt::Display for Bytes<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (index, byte) in self.0.iter().enumerate() {
+            if index != 0 {
+                write!(formatter, " ")?;
+            }
 
-pub extern "x86-interrupt" fn overflow_handler(_frame: Frame) {
-    warn!("Handled the overflow trap.");
-}
+            write!(formatter, "{byte:02x}")?;
+        }
 
-pub extern "x86-interrupt" fn bound_range_exceeded_handler(_frame: Frame) {
-    warn!("Handled the bound range exceeded exception.");
+        Ok(())
+    }
 }
 
-pub extern "x86-interrupt" fn invalid_opcode_handler(_frame: Frame) {
-    warn!("Handled the invalid opcode exception.");
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Policy {
+    Resume,
+    Fixup,
+    Kill,
+    Panic,
 }
 
-pub extern "x86-interrupt" fn device_not_available_handler(_frame: Frame) {
-    warn!("Handled the device not available exception.");
+fn policy(exception: Exception) -> Policy {
+    match exception {
+        Exception::Debug
+        | Exception::NonMaskableInterrupt
+        | Exception::Breakpoint
+        | Exception::Overflow => Policy::Resume,
+        Exception::GeneralProtectionFault | Exception::PageFault => Policy::Fixup,
+        Exception::DivisionError
+        | Exception::BoundRangeExceeded
+        | Exception::InvalidOpcode
+        | Exception::DeviceNotAvailable
+        | Exception::InvalidTss
+        | Exception::SegmentNotPresent
+        | Exception::StackSegmentFault
+        | Exception::X87FloatingPoint
+        | Exception::AlignmentCheck
+        | Exception::SimdFloatingPoint
+        | Exception::ControlProtection => Policy::Kill,
+        Exception::DoubleFault
+        | Exception::CoprocessorSegmentOverrun
+        | Exception::MachineCheck
+        | Exception::Virtualization
+        | Exception::HypervisorInjection
+        | Exception::VmmCommunication
+        | Exception::Security => Policy::Panic,
+    }
 }
 
-pub extern "x86-interrupt" fn double_fault_handler(_frame: Frame, _code: u64) -> ! {
-    error!("Handled the double fault exception.");
-    done();
-}
+fn exception_table() -> &'static [Entry] {
+    let start = &raw const __exception_table_start;
+    let end = &raw const __exception_table_end;
+    let length = (end as usize - start as usize) / size_of::<Entry>();
 
-pub extern "x86-interrupt" fn invalid_tss_handler(_frame: Frame, _code: u64) {
-    warn!("Handled the invalid TSS exception.");
+    unsafe { slice::from_raw_parts(start, length) }
 }
 
-pub extern "x86-interrupt" fn segment_not_present_handler(_frame: Frame, _code: u64) {
-    warn!("Handled the segment not present exception.");
+fn is_canonical(address: u64) -> bool {
+    matches!(address >> 47, 0 | 0x1FFFF)
 }
 
-pub extern "x86-interrupt" fn stack_segment_fault_handler(_frame: Frame, _code: u64) {
-    warn!("Handled the stack segment fault exception.");
-}
+fn opcode(address: u64) -> Option<[u8; OPCODE_BYTES]> {
+    let end = address.checked_add(OPCODE_BYTES as u64 - 1)?;
 
-pub extern "x86-interrupt" fn general_protection_fault_handler(_frame: Frame, _code: u64) {
-    warn!("Handled the general protection fault exception.");
-}
+    if !is_canonical(address) || !is_canonical(end) {
+        return None;
+    }
 
-pub extern "x86-interrupt" fn page_fault_handler(_frame: Frame, _code: u64) {
-    warn!("Handled the page fault exception.");
-}
+    let mapper = memory::mapper();
+    mapper.translate(address)?;
+    mapper.translate(end)?;
 
-pub extern "x86-interrupt" fn x87_floating_point_handler(_frame: Frame) {
-    warn!("Handled the x87 floating-point exception.");
+    Some(unsafe { ptr::read_unaligned(address as *const [u8; OPCODE_BYTES]) })
 }
 
-pub extern "x86-interrupt" fn alignment_check_handler(_frame: Frame, _code: u64) {
-    warn!("Handled the alignment check exception.");
-}
+fn dump(vector: u8, exception: Option<Exception>, frame: &TrapFrame) {
+    match exception {
+        Some(exception) => error!(
+            "Caught the {} exception ({}, vector {vector}) in {} mode.",
+            exception.name(),
+            exception.mnemonic(),
+            if frame.is_user() { "user" } else { "kernel" },
+        ),
+        None => error!("Caught the reserved exception vector {vector}."),
+    }
 
-pub extern "x86-interrupt" fn machine_check_handler(_frame: Frame) -> ! {
-    error!("Handled the machine check exception.");
-    done();
-}
+    match exception {
+        Some(Exception::PageFault) => error!(
+            "Page fault at {:#018x}: {}.",
+            CR2::get(),
+            PageFaultError::new(frame.error_code)
+        ),
+        Some(exception) if exception.has_selector_error() => error!(
+            "Error code {:#x}, selector {}.",
+            frame.error_code,
+            SelectorError::new(frame.error_code)
+        ),
+        _ if trap::has_error_code(vector) => {
+            error!("Error code {:#x}.", frame.error_code);
+        }
+        _ => {}
+    }
 
-pub extern "x86-interrupt" fn simd_floating_point_handler(_frame: Frame) {
-    warn!("Handled the SIMD floating-point exception.");
+    error!(
+        "RIP {:#018x} CS  {:#06x} RFLAGS {:#018x}",
+        frame.instruction_pointer, frame.code_segment, frame.cpu_flags
+    );
+    error!(
+        "RSP {:#018x} SS  {:#06x}",
+        frame.stack_pointer, frame.stack_segment
+    );
+    error!(
+        "RAX {:#018x} RBX {:#018x} RCX {:#018x} RDX {:#018x}",
+        frame.rax, frame.rbx, frame.rcx, frame.rdx
+    );
+    error!(
+        "RSI {:#018x} RDI {:#018x} RBP {:#018x}",
+        frame.rsi, frame.rdi, frame.rbp
+    );
+    error!(
+        "R8  {:#018x} R9  {:#018x} R10 {:#018x} R11 {:#018x}",
+        frame.r8, frame.r9, frame.r10, frame.r11
+    );
+    error!(
+        "R12 {:#018x} R13 {:#018x} R14 {:#018x} R15 {:#018x}",
+        frame.r12, frame.r13, frame.r14, frame.r15
+    );
+    error!(
+        "CR0 {:#018x} CR2 {:#018x} CR3 {:#018x} CR4 {:#018x}",
+        CR0::get(),
+        CR2::get(),
+        CR3::get(),
+        CR4::get()
+    );
+
+    if matches!(
+        exception,
+        Some(Exception::PageFault | Exception::DoubleFault)
+    ) && memory::is_stack_guard(CR2::get())
+    {
+        error!("The fault address is a stack guard page; the stack overflowed.");
+    }
+
+    let Some(bytes) = opcode(frame.instruction_pointer) else {
+        error!("Code: <unavailable>");
+        return;
+    };
+
+    match decoder::decode(&bytes) {
+        Some(instruction) => {
+            let (current, rest) = bytes.split_at(instruction.length());
+            error!("Code: <{}> {}", Bytes(current), Bytes(rest));
+            error!("Instruction: {instruction}.");
+        }
+        None => error!("Code: {} (undecoded)", Bytes(&bytes)),
+    }
 }
 
-pub extern "x86-interrupt" fn virtualization_handler(_frame: Frame) {
-    warn!("Handled the virtualization exception.");
+fn resume(exception: Exception, frame: &TrapFrame) {
+    match exception {
+        Exception::Debug | Exception::Breakpoint => logger::try_log(
+            &Level::Debug,
+            format_args!(
+                "Handled the {} trap at {:#x}.",
+                exception.name(),
+                frame.instruction_pointer
+            ),
+        ),
+        _ => logger::try_log(
+            &Level::Warn,
+            format_args!(
+                "Handled the {} exception at {:#x}.",
+                exception.name(),
+                frame.instruction_pointer
+            ),
+        ),
+    }
 }
 
-pub extern "x86-interrupt" fn control_protection_handler(_frame: Frame, _code: u64) {
-    warn!("Handled the control protection exception.");
+fn fixup(exception: Exception, frame: &mut TrapFrame) -> bool {
+    let Some(target) = fixup::search(exception_table(), frame.instruction_pointer) else {
+        return false;
+    };
+
+    debug!(
+        "Recovered from the {} exception at {:#x} by resuming at {target:#x}.",
+        exception.name(),
+        frame.instruction_pointer
+    );
+    frame.instruction_pointer = target;
+
+    true
 }
 
-pub extern "x86-interrupt" fn hypervisor_injection_handler(_frame: Frame) {
-    warn!("Handled the hypervisor injection exception.");
+fn is_killable(frame: &TrapFrame) -> bool {
+    percpu!(depth) == 1 && (frame.is_user() || thread::is_killable())
 }
 
-pub extern "x86-interrupt" fn vmm_communication_handler(_frame: Frame, _code: u64) {
-    warn!("Handled the VMM communication exception.");
+fn kill(exception: Exception, frame: &TrapFrame) -> ! {
+    dump(exception.vector(), Some(exception), frame);
+
+    assert!(
+        is_killable(frame),
+        "Failed to kill the faulting thread {}.",
+        thread::current()
+    );
+
+    error!(
+        "Killed thread {} after {}.",
+        thread::current(),
+        exception.name()
+    );
+
+    percpu!(depth = 0);
+    thread::exit(usize::MAX);
 }
 
-pub extern "x86-interrupt" fn security_handler(_frame: Frame, _code: u64) {
-    warn!("Handled the security exception.");
+pub fn handle(frame: &mut TrapFrame) {
+    let vector = u8::try_from(frame.vector).expect("Failed to decode the exception vector.");
+
+    let Some(exception) = Exception::from_vector(vector) else {
+        dump(vector, None, frame);
+        panic!("Received the reserved exception vector {vector}.");
+    };
+
+    match policy(exception) {
+        Policy::Resume => resume(exception, frame),
+        Policy::Fixup if !frame.is_user() && fixup(exception, frame) => {}
+        Policy::Fixup | Policy::Kill if is_killable(frame) => {
+            kill(exception, frame);
+        }
+        Policy::Fixup | Policy::Kill | Policy::Panic => {
+            dump(vector, Some(exception), frame);
+            panic!(
+                "Unrecoverable {} exception ({}).",
+                exception.name(),
+                exception.mnemonic()
+            );
+        }
+    }
 }
@@ -0,0 +1,118 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use architecture::x86_64::idt::Status;
+use architecture::x86_64::ps2::{Controller, Decoder};
+use architecture::x86_64::trap::TrapFrame;
+use core::sync::atomic::Ordering::{Acquire, Release};
+use core::sync::atomic::{AtomicU16, AtomicUsize};
+use core::time::Duration;
+use utility::lock::Spinlock;
+use utility::ring::Ring;
+use utility::{info, warn};
+
+use crate::executor::{self, Event};
+use crate::softirq::Tasklet;
+use crate::{interrupt, irq, serial, thread, timer};
+
+const LINE: u8 = 1;
+
+const CAPACITY: usize = 64;
+
+const ENABLE_SCANNING: u8 = 0xF4;
+
+const ACKNOWLEDGE: u8 = 0xFA;
+
+const NO_RESPONSE: u16 = u16::MAX;
+
+const ACKNOWLEDGE_TIMEOUT: Duration = Duration::from_millis(100);
+
+static CONTROLLER: Controller = Controller::new();
+
+static RESPONSE: AtomicU16 = AtomicU16::new(NO_RESPONSE);
+
+static WAITER: AtomicUsize = AtomicUsize::new(0);
+
+static SCANCODES: Spinlock<Ring<u8, CAPACITY>> = Spinlock::new(Ring::new());
+
+static RECEIVED: Event = Event::new();
+
+static SIGNAL: Tasklet = Tasklet::new(|| RECEIVED.signal());
+
+fn acknowledge_handler(_frame: &mut TrapFrame) -> Status {
+    if let Some(response) = CONTROLLER.try_read() {
+        RESPONSE.store(u16::from(response), Release);
+        thread::wake(WAITER.load(Acquire));
+    }
+
+    Status::Handled
+}
+
+fn response() -> Option<u8> {
+    u8::try_from(RESPONSE.load(Acquire)).ok()
+}
+
+fn interrupt_handler(_frame: &mut TrapFrame) -> Status {
+    while let Some(scancode) = CONTROLLER.try_read() {
+        let _ = SCANCODES.lock().push(scancode);
+    }
+
+    SIGNAL.schedule();
+
+    Status::Handled
+}
+
+async fn scancode() -> u8 {
+    loop {
+        if let Some(scancode) = interrupt::without_interrupts(|| SCANCODES.lock().pop()) {
+            return scancode;
+        }
+
+        RECEIVED.wait().await;
+    }
+}
+
+async fn console() {
+    let mut decoder = Decoder::new();
+
+    loop {
+        if let Some(character) = decoder.feed(scancode().await) {
+            serial::input(character);
+        }
+    }
+}
+
+pub fn init() {
+    CONTROLLER.flush();
+    WAITER.store(thread::current(), Release);
+
+    let handle =
+        irq::register(LINE, acknowledge_handler).expect("Failed to register the keyboard handler.");
+
+    CONTROLLER.write(ENABLE_SCANNING);
+
+    if timer::timeout(ACKNOWLEDGE_TIMEOUT, response) != Ok(ACKNOWLEDGE) {
+        let _ = irq::unregister(handle);
+        warn!("Failed to enable scanning on the PS/2 keyboard.");
+        return;
+    }
+
+    interrupt::replace(handle, interrupt_handler).expect("Failed to replace the keyboard handler.");
+
+    executor::spawn(console()).expect("Failed to spawn the keyboard task.");
+
+    info!("Initialized the PS/2 keyboard driver.");
+}
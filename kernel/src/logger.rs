@@ -14,57 +14,73 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use core::cell::LazyCell;
+use architecture::x86_64::serial::Port;
 use core::fmt::{Arguments, Error, Write, write};
 use utility::logging::{Level, Log, set_logger};
 
+use crate::clock;
 use crate::serial::COM1;
 
 struct SerialLogger;
 
 impl Log for SerialLogger {
     fn handler(&self, level: Level, arguments: Arguments<'_>) -> Result<(), Error> {
-        let guard = &mut COM1.lock();
-        let port = LazyCell::force_mut(guard);
+        print(&mut COM1.lock(), &level, arguments)
+    }
+}
+
+fn print(port: &mut Port, level: &Level, arguments: Arguments<'_>) -> Result<(), Error> {
+    let microseconds = clock::nanoseconds() / 1_000;
+    write!(
+        port,
+        "[{:>5}.{:06}] ",
+        microseconds / 1_000_000,
+        microseconds % 1_000_000
+    )?;
 
-        match level {
-            Level::Debug => {
-                write!(port, "\x1b[38;5;34m")?;
-                write!(port, "[DEBUG]")?;
-                write!(port, "\x1b[0m ")?;
-            }
-            Level::Error => {
-                write!(port, "\x1b[38;5;160m")?;
-                write!(port, "[ERROR]")?;
-                write!(port, "\x1b[0m ")?;
-            }
-            Level::Info => {
-                write!(port, "\x1b[38;5;39m")?;
-                write!(port, "[INFO]")?;
-                write!(port, "\x1b[0m ")?;
-            }
-            Level::Trace => {
-                write!(port, "\x1b[38;5;135m")?;
-                write!(port, "[TRACE]")?;
-                write!(port, "\x1b[0m ")?;
-            }
-            Level::Warn => {
-                write!(port, "\x1b[38;5;184m")?;
-                write!(port, "[WARN]")?;
-                write!(port, "\x1b[0m ")?;
-            }
+    match level {
+        Level::Debug => {
+            write!(port, "\x1b[38;5;34m")?;
+            write!(port, "[DEBUG]")?;
+            write!(port, "\x1b[0m ")?;
+        }
+        Level::Error => {
+            write!(port, "\x1b[38;5;160m")?;
+            write!(port, "[ERROR]")?;
+            write!(port, "\x1b[0m ")?;
+        }
+        Level::Info => {
+            write!(port, "\x1b[38;5;39m")?;
+            write!(port, "[INFO]")?;
+            write!(port, "\x1b[0m ")?;
+        }
+        Level::Trace => {
+            write!(port, "\x1b[38;5;135m")?;
+            write!(port, "[TRACE]")?;
+            write!(port, "\x1b[0m ")?;
         }
+        Level::Warn => {
+            write!(port, "\x1b[38;5;184m")?;
+            write!(port, "[WARN]")?;
+            write!(port, "\x1b[0m ")?;
+        }
+    }
 
-        write(port, arguments)?;
+    write(port, arguments)?;
 
-        writeln!(port)?;
+    writeln!(port)?;
 
-        Ok(())
-    }
+    Ok(())
 }
 
 static SERIAL_LOGGER: SerialLogger = SerialLogger;
 
+pub fn try_log(level: &Level, arguments: Arguments<'_>) {
+    if let Some(mut port) = COM1.try_lock() {
+        let _ = print(&mut port, level, arguments);
+    }
+}
+
 pub fn init() {
     set_logger(&SERIAL_LOGGER);
 }
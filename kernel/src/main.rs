@@ -17,20 +17,41 @@
 #![no_main]
 #![no_std]
 #![warn(clippy::pedantic)]
-#![feature(lazy_get)]
-#![feature(abi_x86_interrupt)]
 
+mod acpi;
+mod apic;
 mod boot;
+mod clock;
+mod executor;
 mod gdt;
+mod hpet;
 mod idt;
+mod interrupt;
+mod ioapic;
+mod irq;
 mod isr;
+mod keyboard;
 mod logger;
+mod memory;
+mod percpu;
+mod pic;
+mod pit;
+mod rtc;
 mod serial;
+mod smp;
+mod softirq;
+mod thread;
+mod tick;
+mod time;
+mod timer;
+mod tsc;
 mod tss;
 mod vga;
+mod workqueue;
 
 use architecture::x86_64::instruction;
 use core::panic::PanicInfo;
+use utility::lock::Lazy;
 use utility::{error, info};
 
 #[unsafe(no_mangle)]
@@ -41,22 +62,69 @@ extern "C" fn kmain() -> ! {
 
     boot::init();
 
+    memory::init();
+
+    acpi::init();
+
     gdt::init();
 
+    percpu::init();
+
     tss::init();
 
+    pic::init();
+
+    pit::init();
+
+    tsc::init();
+
+    hpet::init();
+
+    clock::init();
+
+    interrupt::init();
+
     idt::init();
 
+    apic::init();
+
+    ioapic::init();
+
+    rtc::init();
+
+    tick::init();
+
+    thread::init();
+
+    workqueue::init();
+
+    smp::init();
+
+    let executor = executor::init();
+
+    serial::listen();
+
+    keyboard::init();
+
     vga::init();
 
     info!("Successfully initialized the operating system.");
 
-    done();
+    let code = executor.join();
+
+    panic!("Failed to keep the executor thread running (exit code {code}).");
 }
 
 #[panic_handler]
 fn panic(info: &PanicInfo) -> ! {
-    error!("{}", info.message());
+    match Lazy::get(&serial::COM1) {
+        Some(port) => {
+            unsafe { port.force_unlock() };
+
+            error!("{}", info.message());
+        }
+        None => serial::write_unlocked(format_args!("{}\n", info.message())),
+    }
 
     done();
 }
@@ -0,0 +1,167 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use architecture::x86_64::instruction;
+use architecture::x86_64::paging::{Entry, Error, FrameAllocator, Mapper, PAGE_SIZE};
+use architecture::x86_64::register::CR3;
+use bootloader::limine::hhdm;
+use bootloader::limine::memmap::{self, Kind};
+use core::ops::Range;
+use core::sync::atomic::AtomicU64;
+use core::sync::atomic::Ordering::Relaxed;
+use utility::info;
+use utility::lock::Spinlock;
+
+#[used]
+#[unsafe(link_section = ".limine_requests")]
+static HHDM_REQUEST: hhdm::Request = hhdm::Request::new();
+
+#[used]
+#[unsafe(link_section = ".limine_requests")]
+static MEMORY_MAP_REQUEST: memmap::Request = memmap::Request::new();
+
+const LOW_MEMORY: u64 = 0x10_0000;
+
+const STACKS: Range<u64> = 0xFFFF_FE00_0000_0000..0xFFFF_FE80_0000_0000;
+
+static OFFSET: AtomicU64 = AtomicU64::new(0);
+
+static NEXT_STACK: AtomicU64 = AtomicU64::new(STACKS.start);
+
+pub struct Frames {
+    region: usize,
+    next: u64,
+}
+
+impl FrameAllocator for Frames {
+    fn allocate(&mut self) -> Option<u64> {
+        let response = MEMORY_MAP_REQUEST.response()?;
+
+        for (index, entry) in response.entries().enumerate().skip(self.region) {
+            if entry.kind() != Kind::Usable {
+                continue;
+            }
+
+            let start = self.next.max(entry.base()).max(LOW_MEMORY);
+
+            if start + PAGE_SIZE <= entry.base() + entry.length() {
+                self.region = index;
+                self.next = start + PAGE_SIZE;
+                return Some(start);
+            }
+        }
+
+        None
+    }
+}
+
+pub static FRAMES: Spinlock<Frames> = Spinlock::new(Frames { region: 0, next: 0 });
+
+pub fn offset() -> u64 {
+    OFFSET.load(Relaxed)
+}
+
+pub fn mapper() -> Mapper {
+    unsafe { Mapper::new(offset(), CR3::get()) }
+}
+
+pub fn map(address: u64, physical: u64, flags: u64) -> Result<(), Error> {
+    mapper().map(address, physical, flags, &mut *FRAMES.lock())?;
+    instruction::invlpg(address);
+
+    Ok(())
+}
+
+pub fn map_physical(physical: u64, size: u64, flags: u64) -> u64 {
+    let mapper = mapper();
+    let mut page = physical & !(PAGE_SIZE - 1);
+
+    while page < physical + size {
+        let address = offset() + page;
+
+        if mapper.translate(address).is_none() {
+            map(address, page, flags).expect("Failed to map physical memory.");
+        }
+
+        page += PAGE_SIZE;
+    }
+
+    offset() + physical
+}
+
+pub fn map_mmio(physical: u64, size: u64) -> u64 {
+    let flags = Entry::WRITABLE | Entry::WRITE_THROUGH | Entry::CACHE_DISABLE;
+    let mapper = mapper();
+    let mut page = physical & !(PAGE_SIZE - 1);
+
+    while page < physical + size {
+        let address = offset() + page;
+
+        if mapper.translate(address).is_some() {
+            mapper
+                .update(address, flags, &mut *FRAMES.lock())
+                .expect("Failed to remap memory-mapped I/O.");
+            instruction::invlpg(address);
+        } else {
+            map(address, page, flags).expect("Failed to map memory-mapped I/O.");
+        }
+
+        page += PAGE_SIZE;
+    }
+
+    offset() + physical
+}
+
+pub fn allocate_stack(pages: u64) -> u64 {
+    let size = (pages + 1) * PAGE_SIZE;
+    let guard = NEXT_STACK.fetch_add(size, Relaxed);
+    assert!(
+        guard + size <= STACKS.end,
+        "Failed to reserve the stack region."
+    );
+
+    let bottom = guard + PAGE_SIZE;
+
+    for page in 0..pages {
+        let physical = FRAMES
+            .lock()
+            .allocate()
+            .expect("Failed to allocate a stack frame.");
+        map(bottom + page * PAGE_SIZE, physical, Entry::WRITABLE)
+            .expect("Failed to map the stack.");
+    }
+
+    bottom + pages * PAGE_SIZE
+}
+
+pub fn is_stack_guard(address: u64) -> bool {
+    if !STACKS.contains(&address) || address >= NEXT_STACK.load(Relaxed) {
+        return false;
+    }
+
+    mapper().translate(address).is_none()
+}
+
+pub fn init() {
+    let response = HHDM_REQUEST.response().unwrap();
+    assert_eq!(response.revision(), 0);
+    OFFSET.store(response.offset(), Relaxed);
+
+    let response = MEMORY_MAP_REQUEST.response().unwrap();
+    assert_eq!(response.revision(), 0);
+
+    info!("Initialized the memory manager.");
+}
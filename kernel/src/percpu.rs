@@ -0,0 +1,122 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use architecture::x86_64::register::{GsBase, KernelGsBase};
+use core::arch::asm;
+use utility::info;
+
+use crate::smp::MAX_CPUS;
+use crate::tss;
+
+static mut BLOCKS: [Block; MAX_CPUS] = [const { Block::new() }; MAX_CPUS];
+
+#[repr(C)]
+pub struct Block {
+    pub this: usize,
+    pub id: usize,
+    pub thread: usize,
+    pub tss: usize,
+    pub preemption: usize,
+    pub depth: usize,
+    pub pending: usize,
+    pub serving: usize,
+}
+
+impl Block {
+    const fn new() -> Self {
+        Self {
+            this: 0,
+            id: 0,
+            thread: 0,
+            tss: 0,
+            preemption: 0,
+            depth: 0,
+            pending: 0,
+            serving: 0,
+        }
+    }
+}
+
+macro_rules! percpu {
+    ($field:ident) => {
+        $crate::percpu::read(core::mem::offset_of!($crate::percpu::Block, $field))
+    };
+    ($field:ident = $value:expr) => {
+        $crate::percpu::write(core::mem::offset_of!($crate::percpu::Block, $field), $value)
+    };
+    ($field:ident += $value:expr) => {
+        $crate::percpu::add(core::mem::offset_of!($crate::percpu::Block, $field), $value)
+    };
+    ($field:ident -= $value:expr) => {
+        $crate::percpu::sub(core::mem::offset_of!($crate::percpu::Block, $field), $value)
+    };
+    ($field:ident |= $value:expr) => {
+        $crate::percpu::or(core::mem::offset_of!($crate::percpu::Block, $field), $value)
+    };
+}
+
+pub(crate) use percpu;
+
+pub fn read(offset: usize) -> usize {
+    let value: usize;
+    unsafe {
+        asm!("mov {0}, gs:[{1}]", out(reg) value, in(reg) offset);
+    }
+    value
+}
+
+pub fn write(offset: usize, value: usize) {
+    unsafe {
+        asm!("mov gs:[{0}], {1}", in(reg) offset, in(reg) value);
+    }
+}
+
+pub fn add(offset: usize, value: usize) {
+    unsafe {
+        asm!("add gs:[{0}], {1}", in(reg) offset, in(reg) value);
+    }
+}
+
+pub fn sub(offset: usize, value: usize) {
+    unsafe {
+        asm!("sub gs:[{0}], {1}", in(reg) offset, in(reg) value);
+    }
+}
+
+pub fn or(offset: usize, value: usize) {
+    unsafe {
+        asm!("or gs:[{0}], {1}", in(reg) offset, in(reg) value);
+    }
+}
+
+pub fn load(cpu: usize) {
+    let block = unsafe { &raw mut BLOCKS[cpu] };
+
+    unsafe {
+        (*block).this = block as usize;
+        (*block).id = cpu;
+        (*block).tss = tss::segment(cpu) as usize;
+    }
+
+    GsBase::set(block as u64);
+    KernelGsBase::set(0);
+}
+
+pub fn init() {
+    load(0);
+
+    info!("Initialized the per-processor data areas.");
+}
@@ -0,0 +1,96 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use architecture::x86_64::idt::Status;
+use architecture::x86_64::instruction;
+use architecture::x86_64::pit::{self, Timer};
+use architecture::x86_64::trap::TrapFrame;
+use utility::info;
+use utility::lock::Spinlock;
+use utility::time::NANOSECONDS_PER_SECOND;
+
+use crate::{interrupt, irq, tick};
+
+const LINE: u8 = 0;
+
+const CALIBRATION_MILLISECONDS: u64 = 10;
+
+pub static PIT: Spinlock<Timer> = Spinlock::new(Timer::new());
+
+pub fn calibrate(mut read: impl FnMut() -> u64) -> u64 {
+    let ticks = u64::from(pit::FREQUENCY) * CALIBRATION_MILLISECONDS / 1000;
+    let ticks = u16::try_from(ticks).expect("Failed to fit the calibration interval.");
+
+    interrupt::without_interrupts(|| {
+        let timer = PIT.lock();
+        timer.start_gate(ticks);
+
+        let start = read();
+
+        while !timer.is_gate_expired() {
+            instruction::pause();
+        }
+
+        let end = read();
+        timer.stop_gate();
+
+        end.wrapping_sub(start) * 1000 / CALIBRATION_MILLISECONDS
+    })
+}
+
+fn timer_handler(_frame: &mut TrapFrame) -> Status {
+    tick::event();
+
+    Status::Handled
+}
+
+pub struct ClockEvent;
+
+impl tick::Device for ClockEvent {
+    fn name(&self) -> &'static str {
+        "PIT"
+    }
+
+    fn rating(&self) -> Option<u16> {
+        Some(100)
+    }
+
+    fn is_one_shot_capable(&self) -> bool {
+        false
+    }
+
+    fn enable(&self) {
+        irq::register(LINE, timer_handler).expect("Failed to register the PIT handler.");
+    }
+
+    fn set_periodic(&self, frequency: u32) {
+        PIT.lock().set_periodic(frequency);
+    }
+
+    fn set_one_shot(&self, nanoseconds: u64) {
+        let ticks = u128::from(nanoseconds) * u128::from(pit::FREQUENCY)
+            / u128::from(NANOSECONDS_PER_SECOND);
+
+        PIT.lock()
+            .set_one_shot(u16::try_from(ticks.max(1)).unwrap_or(u16::MAX));
+    }
+}
+
+pub fn init() {
+    PIT.lock().set_one_shot(0);
+
+    info!("Initialized the programmable interval timer.");
+}
@@ -0,0 +1,79 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use architecture::x86_64::idt::Status;
+use architecture::x86_64::rtc::{ALARM_INTERRUPT, Clock, PERIODIC_INTERRUPT};
+use architecture::x86_64::trap::TrapFrame;
+use core::sync::atomic::AtomicU64;
+use core::sync::atomic::Ordering::Relaxed;
+use utility::lock::Spinlock;
+use utility::time::DateTime;
+use utility::{info, warn};
+
+use crate::time::{self, SystemTime};
+use crate::workqueue::{self, Work};
+use crate::{acpi, irq};
+
+const LINE: u8 = 8;
+
+pub static RTC: Spinlock<Clock> = Spinlock::new(Clock::new(None));
+
+static PERIODIC: AtomicU64 = AtomicU64::new(0);
+
+fn interrupt_handler(_frame: &mut TrapFrame) -> Status {
+    let status = RTC.lock().acknowledge();
+
+    if status & PERIODIC_INTERRUPT != 0 {
+        PERIODIC.fetch_add(1, Relaxed);
+    }
+
+    if status & ALARM_INTERRUPT != 0 {
+        let _ = workqueue::SYSTEM.queue(Work::new(report_alarm, 0));
+    }
+
+    Status::Handled
+}
+
+fn report_alarm(_: usize) {
+    info!("The real-time clock alarm fired.");
+}
+
+pub fn init() {
+    *RTC.lock() = Clock::new(acpi::century());
+
+    let reading = RTC.lock().now();
+    let date_time = DateTime::new(
+        reading.year,
+        reading.month,
+        reading.day,
+        reading.hour,
+        reading.minute,
+        reading.second,
+    );
+
+    match date_time {
+        Some(date_time) => time::set(date_time),
+        None => warn!("Failed to validate the date reported by the real-time clock."),
+    }
+
+    irq::register(LINE, interrupt_handler).expect("Failed to register the RTC handler.");
+    let _ = RTC.lock().acknowledge();
+
+    info!(
+        "Initialized the real-time clock at {}.",
+        SystemTime::now().date_time()
+    );
+}
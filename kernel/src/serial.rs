@@ -14,18 +14,38 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use architecture::x86_64::idt::Status;
 use architecture::x86_64::serial::{Port, Ports};
-use core::cell::LazyCell;
-use core::fmt::{Result, Write};
-use utility::lock::Spinlock;
+use architecture::x86_64::trap::TrapFrame;
+use core::fmt::{self, Arguments, Result, Write};
+use utility::lock::{IrqSpinlock, Lazy};
 
-use crate::info;
+use crate::executor::{self, Event};
+use crate::softirq::Tasklet;
+use crate::{info, irq, thread};
 
-pub static COM1: Spinlock<LazyCell<Port>> = Spinlock::new(LazyCell::new(|| Port::new(Ports::COM1)));
+const LINE: u8 = 4;
+
+const STATUS: char = '\x14';
+
+pub static COM1: Lazy<IrqSpinlock<Port>> = Lazy::new(|| IrqSpinlock::new(Port::new(Ports::COM1)));
+
+static RECEIVED: Event = Event::new();
+
+static SIGNAL: Tasklet = Tasklet::new(|| RECEIVED.signal());
+
+fn interrupt_handler(_frame: &mut TrapFrame) -> Status {
+    SIGNAL.schedule();
+
+    Status::Handled
+}
+
+pub fn write_unlocked(arguments: Arguments<'_>) {
+    let _ = fmt::write(&mut Port::uninitialized(Ports::COM1), arguments);
+}
 
 pub fn setup_title() -> Result {
-    let guard = &mut COM1.lock();
-    let port = LazyCell::force_mut(guard);
+    let port = &mut *COM1.lock();
 
     write!(port, "\x1b[38;5;202m")?;
     write!(port, "\x1b[1m")?;
@@ -36,6 +56,49 @@ pub fn setup_title() -> Result {
     Ok(())
 }
 
+pub async fn receive() -> u8 {
+    loop {
+        if let Some(byte) = COM1.lock().try_read() {
+            return byte;
+        }
+
+        RECEIVED.wait().await;
+    }
+}
+
+pub fn input(character: char) {
+    if character == STATUS {
+        thread::report();
+        return;
+    }
+
+    let port = COM1.lock();
+
+    if character == '\n' {
+        port.write('\r');
+    }
+
+    port.write(character);
+}
+
+async fn console() {
+    loop {
+        let byte = receive().await;
+        input(char::from(byte));
+
+        executor::yield_now().await;
+    }
+}
+
+pub fn listen() {
+    irq::register(LINE, interrupt_handler).expect("Failed to register the serial handler.");
+    COM1.lock().enable_receive_interrupt();
+
+    executor::spawn(console()).expect("Failed to spawn the serial console task.");
+
+    info!("Started the serial console.");
+}
+
 pub fn init() {
     setup_title().expect("Failed to setup title bar.");
 
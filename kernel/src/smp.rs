@@ -0,0 +1,146 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use architecture::x86_64::apic::{Delivery, Destination};
+use architecture::x86_64::idt::{Options, Status};
+use architecture::x86_64::instruction;
+use architecture::x86_64::trap::TrapFrame;
+use bootloader::limine::mp::{self, Cpu};
+use core::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+use core::sync::atomic::{AtomicU32, AtomicUsize};
+use core::time::Duration;
+use utility::{info, warn};
+
+use crate::idt::IDT;
+use crate::percpu::{self, percpu};
+use crate::{apic, clock, gdt, interrupt, thread, tick, timer, tss};
+
+pub const MAX_CPUS: usize = 16;
+
+pub const RESCHEDULE_VECTOR: u8 = 0xFB;
+
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(1);
+
+const STARTUP_INTERVAL: Duration = Duration::from_millis(1);
+
+#[used]
+#[unsafe(link_section = ".limine_requests")]
+static MP_REQUEST: mp::Request = mp::Request::new();
+
+static LAPIC_IDS: [AtomicU32; MAX_CPUS] = [const { AtomicU32::new(u32::MAX) }; MAX_CPUS];
+
+static ONLINE: AtomicUsize = AtomicUsize::new(1);
+
+pub fn id() -> usize {
+    percpu!(id)
+}
+
+pub fn send_reschedule(cpu: usize) {
+    let lapic_id = LAPIC_IDS[cpu].load(Relaxed);
+
+    apic::local().send_ipi(
+        Destination::Single(lapic_id),
+        Delivery::Fixed(RESCHEDULE_VECTOR),
+    );
+}
+
+fn reschedule_handler(_frame: &mut TrapFrame) -> Status {
+    tick::rearm();
+    apic::local().end_of_interrupt();
+
+    Status::Handled
+}
+
+extern "C" fn start(cpu: &'static Cpu) -> ! {
+    let index = usize::try_from(cpu.extra_argument()).expect("Failed to decode the processor.");
+
+    gdt::load(index);
+    percpu::load(index);
+    tss::load(index);
+    IDT.read().load();
+
+    apic::init_secondary();
+
+    thread::init_secondary(index);
+
+    tick::init_secondary();
+
+    ONLINE.fetch_add(1, Release);
+
+    info!("Started processor {index} (local APIC {}).", cpu.lapic_id());
+
+    instruction::sti();
+
+    loop {
+        instruction::hlt();
+    }
+}
+
+pub fn init() {
+    let Some(response) = MP_REQUEST.response() else {
+        warn!("Failed to receive the multiprocessor response; running on one processor.");
+        return;
+    };
+
+    interrupt::register(RESCHEDULE_VECTOR, Options::new(), reschedule_handler)
+        .expect("Failed to register the reschedule handler.");
+
+    LAPIC_IDS[0].store(response.bsp_lapic_id(), Relaxed);
+
+    let mut count = 1;
+    for cpu in response.cpus() {
+        if cpu.lapic_id() == response.bsp_lapic_id() {
+            continue;
+        }
+
+        if count == MAX_CPUS {
+            warn!(
+                "Ignored processor {} beyond the limit of {MAX_CPUS}.",
+                cpu.processor_id()
+            );
+            continue;
+        }
+
+        LAPIC_IDS[count].store(cpu.lapic_id(), Relaxed);
+        count += 1;
+    }
+
+    for cpu in response.cpus() {
+        if let Some(index) = LAPIC_IDS[1..count]
+            .iter()
+            .position(|id| id.load(Relaxed) == cpu.lapic_id())
+        {
+            cpu.start(start, (index + 1) as u64);
+        }
+    }
+
+    let deadline = timer::deadline(STARTUP_TIMEOUT);
+    while ONLINE.load(Acquire) < count && clock::nanoseconds() < deadline {
+        timer::sleep(STARTUP_INTERVAL);
+    }
+
+    if ONLINE.load(Acquire) < count {
+        warn!(
+            "Started only {} of {count} processors.",
+            ONLINE.load(Acquire)
+        );
+    }
+
+    info!(
+        "Initialized symmetric multiprocessing with {} processors.",
+        ONLINE.load(Acquire)
+    );
+}
@@ -0,0 +1,129 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use architecture::x86_64::instruction;
+use core::sync::atomic::AtomicBool;
+use core::sync::atomic::Ordering::{AcqRel, Acquire, Release};
+use utility::lock::Spinlock;
+use utility::ring::Ring;
+
+use crate::percpu::percpu;
+use crate::smp::{self, MAX_CPUS};
+use crate::{interrupt, timer};
+
+const RESTART_LIMIT: usize = 10;
+
+const TASKLET_CAPACITY: usize = 64;
+
+static HANDLERS: [fn(); 2] = [timer::run, run_tasklets];
+
+static TASKLETS: [Spinlock<Ring<&Tasklet, TASKLET_CAPACITY>>; MAX_CPUS] =
+    [const { Spinlock::new(Ring::new()) }; MAX_CPUS];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Softirq {
+    Timer,
+    Tasklet,
+}
+
+pub struct Tasklet {
+    function: fn(),
+    scheduled: AtomicBool,
+    running: AtomicBool,
+}
+
+impl Tasklet {
+    pub const fn new(function: fn()) -> Self {
+        Self {
+            function,
+            scheduled: AtomicBool::new(false),
+            running: AtomicBool::new(false),
+        }
+    }
+
+    pub fn schedule(&'static self) {
+        if self.scheduled.swap(true, AcqRel) {
+            return;
+        }
+
+        enqueue(self);
+    }
+}
+
+fn enqueue(tasklet: &'static Tasklet) {
+    interrupt::without_interrupts(|| {
+        TASKLETS[smp::id()]
+            .lock()
+            .push(tasklet)
+            .unwrap_or_else(|_| panic!("Failed to queue a tasklet."));
+    });
+
+    raise(Softirq::Tasklet);
+}
+
+fn run_tasklets() {
+    let cpu = smp::id();
+    let count = interrupt::without_interrupts(|| TASKLETS[cpu].lock().len());
+
+    for _ in 0..count {
+        let Some(tasklet) = interrupt::without_interrupts(|| TASKLETS[cpu].lock().pop()) else {
+            break;
+        };
+
+        if tasklet.running.swap(true, Acquire) {
+            enqueue(tasklet);
+            continue;
+        }
+
+        tasklet.scheduled.store(false, Release);
+        (tasklet.function)();
+        tasklet.running.store(false, Release);
+    }
+}
+
+pub fn raise(softirq: Softirq) {
+    percpu!(pending |= 1 << softirq as usize);
+}
+
+pub fn run() {
+    if percpu!(serving) != 0 || percpu!(pending) == 0 {
+        return;
+    }
+
+    percpu!(serving = 1);
+    percpu!(preemption += 1);
+
+    for _ in 0..RESTART_LIMIT {
+        let pending = percpu!(pending);
+        if pending == 0 {
+            break;
+        }
+
+        percpu!(pending = 0);
+        instruction::sti();
+
+        for (index, handler) in HANDLERS.iter().enumerate() {
+            if pending & (1 << index) != 0 {
+                handler();
+            }
+        }
+
+        instruction::cli();
+    }
+
+    percpu!(preemption -= 1);
+    percpu!(serving = 0);
+}
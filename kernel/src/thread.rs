@@ -0,0 +1,846 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use architecture::x86_64::context::{self, FpuState};
+use architecture::x86_64::instruction;
+use core::mem;
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering::Relaxed;
+use core::time::Duration;
+use utility::lock::{Guard, Spinlock};
+use utility::queue::{LEVELS, RunQueue};
+use utility::{debug, info};
+
+use crate::percpu::percpu;
+use crate::smp::{self, MAX_CPUS};
+use crate::{clock, interrupt, memory, tick, tss};
+
+const CAPACITY: usize = 64;
+
+const STACK_PAGES: u64 = 4;
+
+const SLICE_TICKS: u32 = 2;
+
+const BOOST_TICKS: u64 = 1000;
+
+const BALANCE_TICKS: u64 = 100;
+
+pub const NICE_MIN: i8 = -20;
+
+pub const NICE_MAX: i8 = 19;
+
+pub const ANY_CPU: u64 = u64::MAX;
+
+static TABLE: Spinlock<Table> = Spinlock::new(Table {
+    threads: [const { Thread::new() }; CAPACITY],
+    cpus: [const { Cpu::new() }; MAX_CPUS],
+});
+
+static QUEUES: [Queue; MAX_CPUS] = [const { Queue::new() }; MAX_CPUS];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    Full,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Free,
+    Ready,
+    Running,
+    Sleeping,
+    Blocked,
+    Exited,
+}
+
+struct Thread {
+    name: &'static str,
+    state: State,
+    generation: u32,
+    detached: bool,
+    idle: bool,
+    woken: bool,
+    cpu: usize,
+    affinity: u64,
+    nice: i8,
+    level: usize,
+    slice: u32,
+    started: u64,
+    runtime: u64,
+    switches: u64,
+    stack: u64,
+    stack_pointer: u64,
+    fpu: FpuState,
+    entry: Option<fn(usize) -> usize>,
+    argument: usize,
+    exit_code: usize,
+    joiner: Option<usize>,
+}
+
+impl Thread {
+    const fn new() -> Self {
+        Self {
+            name: "",
+            state: State::Free,
+            generation: 0,
+            detached: false,
+            idle: false,
+            woken: false,
+            cpu: 0,
+            affinity: ANY_CPU,
+            nice: 0,
+            level: base_level(0),
+            slice: 0,
+            started: 0,
+            runtime: 0,
+            switches: 0,
+            stack: 0,
+            stack_pointer: 0,
+            fpu: FpuState::new(),
+            entry: None,
+            argument: 0,
+            exit_code: 0,
+            joiner: None,
+        }
+    }
+
+    fn release(&mut self) {
+        self.state = State::Free;
+        self.generation = self.generation.wrapping_add(1);
+        self.detached = false;
+        self.woken = false;
+        self.entry = None;
+        self.joiner = None;
+    }
+
+    fn runtime(&self, now: u64) -> u64 {
+        if self.state == State::Running {
+            self.runtime + now.saturating_sub(self.started)
+        } else {
+            self.runtime
+        }
+    }
+
+    fn allows(&self, cpu: usize) -> bool {
+        self.affinity & (1 << cpu) != 0
+    }
+}
+
+struct Cpu {
+    current: usize,
+    idle: usize,
+    ticks: u64,
+    started: u64,
+    reschedule: bool,
+    online: bool,
+}
+
+impl Cpu {
+    const fn new() -> Self {
+        Self {
+            current: 0,
+            idle: 0,
+            ticks: 0,
+            started: 0,
+            reschedule: false,
+            online: false,
+        }
+    }
+}
+
+struct Queue {
+    threads: Spinlock<RunQueue<CAPACITY>>,
+    length: AtomicUsize,
+}
+
+impl Queue {
+    const fn new() -> Self {
+        Self {
+            threads: Spinlock::new(RunQueue::new()),
+            length: AtomicUsize::new(0),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.length.load(Relaxed)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn update<R>(&self, function: impl FnOnce(&mut RunQueue<CAPACITY>) -> R) -> R {
+        let mut threads = self.threads.lock();
+        let result = function(&mut threads);
+        self.length.store(threads.len(), Relaxed);
+        result
+    }
+
+    fn push(&self, index: usize, level: usize) {
+        self.update(|threads| threads.push(index, level));
+    }
+
+    fn pop(&self) -> Option<usize> {
+        self.update(RunQueue::pop)
+    }
+
+    fn remove(&self, index: usize) -> bool {
+        self.update(|threads| threads.remove(index))
+    }
+
+    fn requeue(&self, index: usize, level: usize) {
+        self.update(|threads| {
+            if threads.remove(index) {
+                threads.push(index, level);
+            }
+        });
+    }
+
+    fn take(&self, predicate: impl FnMut(usize) -> bool) -> Option<usize> {
+        self.update(|threads| {
+            let index = threads.find(predicate)?;
+            threads.remove(index);
+            Some(index)
+        })
+    }
+}
+
+struct Table {
+    threads: [Thread; CAPACITY],
+    cpus: [Cpu; MAX_CPUS],
+}
+
+impl Table {
+    fn is_live(&self, index: usize, generation: u32) -> bool {
+        self.threads
+            .get(index)
+            .is_some_and(|thread| thread.generation == generation && thread.state != State::Free)
+    }
+
+    fn claim(&self) -> Result<usize, Error> {
+        self.threads
+            .iter()
+            .position(|thread| thread.state == State::Free)
+            .ok_or(Error::Full)
+    }
+
+    fn current(&self, cpu: usize) -> usize {
+        self.cpus[cpu].current
+    }
+
+    fn load(&self, cpu: usize) -> usize {
+        QUEUES[cpu].len() + usize::from(self.current(cpu) != self.cpus[cpu].idle)
+    }
+
+    fn place(&self, index: usize) -> usize {
+        let thread = &self.threads[index];
+
+        (0..MAX_CPUS)
+            .filter(|&cpu| self.cpus[cpu].online && thread.allows(cpu))
+            .min_by_key(|&cpu| (self.load(cpu), cpu != thread.cpu))
+            .unwrap_or(thread.cpu)
+    }
+
+    fn enqueue(&mut self, index: usize, cpu: usize) {
+        let thread = &mut self.threads[index];
+        thread.state = State::Ready;
+        thread.cpu = cpu;
+
+        if !thread.idle {
+            QUEUES[cpu].push(index, thread.level);
+        }
+    }
+
+    fn requeue(&mut self, index: usize, cpu: usize) {
+        if self.threads[index].allows(cpu) {
+            self.enqueue(index, cpu);
+        } else {
+            let target = self.place(index);
+            self.enqueue(index, target);
+        }
+    }
+
+    fn ready(&mut self, index: usize, cpu: usize) {
+        let target = self.place(index);
+        self.enqueue(index, target);
+
+        let current = self.current(target);
+        if current != self.cpus[target].idle
+            && self.threads[index].level >= self.threads[current].level
+        {
+            return;
+        }
+
+        self.cpus[target].reschedule = true;
+
+        if target != cpu {
+            smp::send_reschedule(target);
+        }
+    }
+
+    fn suspend(&mut self, cpu: usize, state: State) {
+        let current = self.current(cpu);
+        self.threads[current].state = state;
+    }
+
+    fn boost(&mut self) {
+        for index in 0..CAPACITY {
+            let thread = &mut self.threads[index];
+            thread.level = base_level(thread.nice);
+
+            QUEUES[thread.cpu].requeue(index, thread.level);
+        }
+    }
+
+    fn busiest(&self, cpu: usize) -> Option<usize> {
+        (0..MAX_CPUS)
+            .filter(|&other| other != cpu && self.cpus[other].online)
+            .max_by_key(|&other| QUEUES[other].len())
+    }
+
+    fn migrate(&mut self, source: usize, cpu: usize) -> Option<usize> {
+        let threads = &self.threads;
+        let index = QUEUES[source].take(|index| threads[index].allows(cpu))?;
+
+        self.threads[index].cpu = cpu;
+
+        Some(index)
+    }
+
+    fn steal(&mut self, cpu: usize) -> Option<usize> {
+        let source = self.busiest(cpu)?;
+        self.migrate(source, cpu)
+    }
+
+    fn balance(&mut self, cpu: usize) {
+        let Some(source) = self.busiest(cpu) else {
+            return;
+        };
+
+        if QUEUES[source].len() <= QUEUES[cpu].len() + 1 {
+            return;
+        }
+
+        if let Some(index) = self.migrate(source, cpu) {
+            self.enqueue(index, cpu);
+            self.cpus[cpu].reschedule |= self.current(cpu) == self.cpus[cpu].idle;
+        }
+    }
+}
+
+pub struct JoinHandle {
+    index: usize,
+    generation: u32,
+}
+
+impl JoinHandle {
+    #[must_use]
+    pub fn id(&self) -> usize {
+        id(self.index, self.generation)
+    }
+
+    pub fn set_nice(&self, nice: i8) {
+        let nice = nice.clamp(NICE_MIN, NICE_MAX);
+
+        interrupt::without_interrupts(|| {
+            let mut guard = TABLE.lock();
+            let table = &mut *guard;
+            if !table.is_live(self.index, self.generation) {
+                return;
+            }
+
+            let thread = &mut table.threads[self.index];
+            thread.nice = nice;
+            thread.level = base_level(nice);
+
+            QUEUES[thread.cpu].requeue(self.index, thread.level);
+        });
+    }
+
+    pub fn set_affinity(&self, affinity: u64) {
+        assert!(affinity != 0, "Failed to set an empty CPU affinity mask.");
+
+        interrupt::without_interrupts(|| {
+            let mut table = TABLE.lock();
+            if !table.is_live(self.index, self.generation) {
+                return;
+            }
+
+            let thread = &mut table.threads[self.index];
+            thread.affinity = affinity;
+
+            let cpu = thread.cpu;
+            if thread.allows(cpu) {
+                return;
+            }
+
+            match thread.state {
+                State::Ready => {
+                    QUEUES[cpu].remove(self.index);
+                    table.ready(self.index, smp::id());
+                }
+                State::Running => {
+                    table.cpus[cpu].reschedule = true;
+
+                    if cpu != smp::id() {
+                        smp::send_reschedule(cpu);
+                    }
+                }
+                _ => {}
+            }
+        });
+    }
+
+    pub fn join(self) -> usize {
+        interrupt::without_interrupts(|| {
+            loop {
+                let mut table = TABLE.lock();
+                assert!(
+                    table.is_live(self.index, self.generation),
+                    "Failed to join a thread that no longer exists."
+                );
+
+                let cpu = smp::id();
+                let current = table.current(cpu);
+                let thread = &mut table.threads[self.index];
+
+                if thread.state == State::Exited {
+                    let code = thread.exit_code;
+                    thread.release();
+                    return code;
+                }
+
+                thread.joiner = Some(current);
+                table.suspend(cpu, State::Blocked);
+                schedule(table, cpu);
+            }
+        })
+    }
+}
+
+impl Drop for JoinHandle {
+    fn drop(&mut self) {
+        interrupt::without_interrupts(|| {
+            let mut table = TABLE.lock();
+            if !table.is_live(self.index, self.generation) {
+                return;
+            }
+
+            let thread = &mut table.threads[self.index];
+            if thread.state == State::Exited {
+                thread.release();
+            } else {
+                thread.detached = true;
+            }
+        });
+    }
+}
+
+const fn base_level(nice: i8) -> usize {
+    (nice.abs_diff(NICE_MIN) as usize * LEVELS) / (NICE_MAX.abs_diff(NICE_MIN) as usize + 1)
+}
+
+const fn slice(level: usize) -> u32 {
+    SLICE_TICKS << level
+}
+
+fn id(index: usize, generation: u32) -> usize {
+    (generation as usize) << 32 | index
+}
+
+fn split(id: usize) -> (usize, u32) {
+    (
+        id & 0xFFFF_FFFF,
+        u32::try_from(id >> 32).unwrap_or(u32::MAX),
+    )
+}
+
+pub fn current() -> usize {
+    percpu!(thread)
+}
+
+pub fn is_killable() -> bool {
+    interrupt::without_interrupts(|| {
+        let Some(table) = TABLE.try_lock() else {
+            return false;
+        };
+
+        let cpu = smp::id();
+        let thread = &table.threads[table.current(cpu)];
+
+        table.cpus[cpu].online && thread.stack != 0 && !thread.idle
+    })
+}
+
+fn create(
+    table: &mut Table,
+    name: &'static str,
+    entry: fn(usize) -> usize,
+    argument: usize,
+) -> Result<usize, Error> {
+    let index = table.claim()?;
+    let thread = &mut table.threads[index];
+
+    if thread.stack == 0 {
+        thread.stack = memory::allocate_stack(STACK_PAGES);
+    }
+
+    thread.name = name;
+    thread.entry = Some(entry);
+    thread.argument = argument;
+    thread.exit_code = 0;
+    thread.idle = false;
+    thread.affinity = ANY_CPU;
+    thread.nice = 0;
+    thread.level = base_level(0);
+    thread.runtime = 0;
+    thread.switches = 0;
+    thread.fpu = FpuState::new();
+    thread.stack_pointer = unsafe { context::prepare(thread.stack, start, index) };
+
+    Ok(index)
+}
+
+pub fn spawn(
+    name: &'static str,
+    entry: fn(usize) -> usize,
+    argument: usize,
+) -> Result<JoinHandle, Error> {
+    interrupt::without_interrupts(|| {
+        let mut table = TABLE.lock();
+
+        let index = create(&mut table, name, entry, argument)?;
+        table.ready(index, smp::id());
+
+        Ok(JoinHandle {
+            index,
+            generation: table.threads[index].generation,
+        })
+    })
+}
+
+pub fn exit(code: usize) -> ! {
+    interrupt::without_interrupts(|| {
+        let mut table = TABLE.lock();
+        let cpu = smp::id();
+        let current = table.current(cpu);
+        let thread = &mut table.threads[current];
+
+        assert!(
+            thread.stack != 0 && !thread.idle,
+            "Failed to exit the boot or idle thread."
+        );
+
+        debug!(
+            "Thread {current} ({}) exited with code {code}.",
+            thread.name
+        );
+
+        thread.exit_code = code;
+        let joiner = thread.joiner.take();
+
+        if thread.detached {
+            thread.release();
+        } else {
+            thread.state = State::Exited;
+        }
+
+        if let Some(joiner) = joiner {
+            table.ready(joiner, cpu);
+        }
+
+        schedule(table, cpu);
+    });
+
+    unreachable!("Failed to stop an exited thread.");
+}
+
+pub fn yield_now() {
+    interrupt::without_interrupts(|| {
+        let mut table = TABLE.lock();
+        let cpu = smp::id();
+        let current = table.current(cpu);
+        table.requeue(current, cpu);
+        schedule(table, cpu);
+    });
+}
+
+pub fn sleep() {
+    interrupt::without_interrupts(|| {
+        let mut table = TABLE.lock();
+        let cpu = smp::id();
+        let current = table.current(cpu);
+
+        if mem::take(&mut table.threads[current].woken) {
+            return;
+        }
+
+        table.suspend(cpu, State::Sleeping);
+        schedule(table, cpu);
+    });
+}
+
+pub fn wake(id: usize) {
+    let (index, generation) = split(id);
+
+    interrupt::without_interrupts(|| {
+        let mut table = TABLE.lock();
+        if !table.is_live(index, generation) {
+            return;
+        }
+
+        match table.threads[index].state {
+            State::Blocked | State::Sleeping => table.ready(index, smp::id()),
+            State::Ready | State::Running => table.threads[index].woken = true,
+            State::Free | State::Exited => {}
+        }
+    });
+}
+
+pub fn tick(ticks: u64) {
+    let mut table = TABLE.lock();
+    let cpu = smp::id();
+    if !table.cpus[cpu].online {
+        return;
+    }
+
+    let previous = table.cpus[cpu].ticks;
+    table.cpus[cpu].ticks += ticks;
+    let total = table.cpus[cpu].ticks;
+
+    if cpu == 0 && previous / BOOST_TICKS != total / BOOST_TICKS {
+        table.boost();
+    }
+
+    if previous / BALANCE_TICKS != total / BALANCE_TICKS {
+        table.balance(cpu);
+    }
+
+    let current = table.current(cpu);
+    if current == table.cpus[cpu].idle {
+        table.cpus[cpu].reschedule |= !QUEUES[cpu].is_empty();
+        return;
+    }
+
+    let thread = &mut table.threads[current];
+    let ran = clock::nanoseconds().saturating_sub(thread.started) / tick::PERIOD;
+    let elapsed = u32::try_from(ticks.min(ran.max(1))).unwrap_or(u32::MAX);
+    thread.slice = thread.slice.saturating_sub(elapsed);
+
+    if thread.slice == 0 {
+        thread.level = (thread.level + 1).min(LEVELS - 1);
+        table.cpus[cpu].reschedule = true;
+    }
+}
+
+pub fn preempt() {
+    let mut table = TABLE.lock();
+    let cpu = smp::id();
+    if !table.cpus[cpu].online || !table.cpus[cpu].reschedule || percpu!(preemption) != 0 {
+        return;
+    }
+
+    let current = table.current(cpu);
+    if table.threads[current].state == State::Running {
+        table.requeue(current, cpu);
+    }
+
+    schedule(table, cpu);
+}
+
+fn schedule(mut table: Guard<Table>, cpu: usize) {
+    let marker = 0u8;
+    assert!(
+        !tss::is_interrupt_stack(cpu, &raw const marker as u64),
+        "Failed to schedule from an interrupt stack."
+    );
+
+    let now = clock::nanoseconds();
+    let previous = table.current(cpu);
+    let next = QUEUES[cpu]
+        .pop()
+        .or_else(|| table.steal(cpu))
+        .unwrap_or(table.cpus[cpu].idle);
+
+    let thread = &mut table.threads[previous];
+    thread.runtime += now.saturating_sub(thread.started);
+
+    let thread = &mut table.threads[next];
+    thread.state = State::Running;
+    thread.cpu = cpu;
+    thread.started = now;
+    thread.slice = slice(thread.level);
+
+    table.cpus[cpu].current = next;
+    table.cpus[cpu].reschedule = false;
+    percpu!(thread = id(next, table.threads[next].generation));
+
+    let slice = (next != table.cpus[cpu].idle).then_some(table.threads[next].slice);
+    tick::set_slice(now, slice);
+
+    if next == previous {
+        return;
+    }
+
+    table.threads[next].switches += 1;
+
+    let threads = table.threads.as_mut_ptr();
+    mem::forget(table);
+
+    unsafe {
+        let previous = threads.add(previous);
+        let next = threads.add(next);
+
+        (*previous).fpu.save();
+        (*next).fpu.restore();
+        context::switch_to(&raw mut (*previous).stack_pointer, (*next).stack_pointer);
+
+        TABLE.force_unlock();
+    }
+}
+
+extern "C" fn start(index: usize) -> ! {
+    unsafe {
+        TABLE.force_unlock();
+    }
+
+    let (entry, argument) = interrupt::without_interrupts(|| {
+        let table = TABLE.lock();
+        let thread = &table.threads[index];
+        (thread.entry, thread.argument)
+    });
+
+    instruction::sti();
+
+    let entry = entry.expect("Failed to find the thread entry point.");
+    exit(entry(argument));
+}
+
+fn idle(_: usize) -> usize {
+    loop {
+        instruction::hlt();
+    }
+}
+
+pub fn report() {
+    let now = clock::nanoseconds();
+
+    for index in 0..CAPACITY {
+        let (name, state, cpu, nice, level, runtime, switches) =
+            interrupt::without_interrupts(|| {
+                let table = TABLE.lock();
+                let thread = &table.threads[index];
+                (
+                    thread.name,
+                    thread.state,
+                    thread.cpu,
+                    thread.nice,
+                    thread.level,
+                    thread.runtime(now),
+                    thread.switches,
+                )
+            });
+
+        if state == State::Free {
+            continue;
+        }
+
+        info!(
+            "Thread {index} ({name}): {state:?} on CPU {cpu}, nice {nice}, level {level}, CPU time {:?}, {switches} switches.",
+            Duration::from_nanos(runtime)
+        );
+    }
+
+    for cpu in 0..MAX_CPUS {
+        let (online, elapsed, idle) = interrupt::without_interrupts(|| {
+            let table = TABLE.lock();
+            let state = &table.cpus[cpu];
+            (
+                state.online,
+                now.saturating_sub(state.started),
+                table.threads[state.idle].runtime(now),
+            )
+        });
+
+        if !online {
+            continue;
+        }
+
+        let busy = elapsed.saturating_sub(idle);
+        info!(
+            "CPU {cpu}: {}% busy, {:?} busy, {:?} idle.",
+            busy * 100 / elapsed.max(1),
+            Duration::from_nanos(busy),
+            Duration::from_nanos(idle)
+        );
+    }
+}
+
+pub fn init_secondary(cpu: usize) {
+    interrupt::without_interrupts(|| {
+        let mut table = TABLE.lock();
+        let index = table
+            .claim()
+            .expect("Failed to claim an idle thread for the processor.");
+        let now = clock::nanoseconds();
+
+        let thread = &mut table.threads[index];
+        thread.name = "idle";
+        thread.state = State::Running;
+        thread.idle = true;
+        thread.cpu = cpu;
+        thread.affinity = 1 << cpu;
+        thread.runtime = 0;
+        thread.started = now;
+
+        let state = &mut table.cpus[cpu];
+        state.current = index;
+        state.idle = index;
+        state.started = now;
+        state.online = true;
+
+        percpu!(thread = id(index, table.threads[index].generation));
+    });
+}
+
+pub fn init() {
+    interrupt::without_interrupts(|| {
+        let mut table = TABLE.lock();
+        let now = clock::nanoseconds();
+
+        let thread = &mut table.threads[0];
+        thread.name = "main";
+        thread.state = State::Running;
+        thread.started = now;
+        thread.slice = slice(thread.level);
+
+        let index = create(&mut table, "idle", idle, 0).expect("Failed to spawn the idle thread.");
+        let thread = &mut table.threads[index];
+        thread.idle = true;
+        thread.affinity = 1;
+        thread.state = State::Ready;
+
+        let state = &mut table.cpus[0];
+        state.current = 0;
+        state.idle = index;
+        state.started = now;
+        state.online = true;
+
+        percpu!(thread = id(0, table.threads[0].generation));
+    });
+
+    info!("Initialized the preemptive scheduler.");
+}
@@ -0,0 +1,198 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::sync::atomic::Ordering::{Relaxed, SeqCst};
+use core::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize};
+use utility::time::NANOSECONDS_PER_SECOND;
+use utility::{info, warn};
+
+use crate::smp::{self, MAX_CPUS};
+use crate::softirq::{self, Softirq};
+use crate::{apic, clock, hpet, pit, thread, timer};
+
+pub const FREQUENCY: u32 = 1000;
+
+pub const PERIOD: u64 = NANOSECONDS_PER_SECOND / FREQUENCY as u64;
+
+const IDLE_TICKS: u64 = 100;
+
+pub trait Device: Sync {
+    fn name(&self) -> &'static str;
+
+    fn rating(&self) -> Option<u16>;
+
+    fn is_one_shot_capable(&self) -> bool;
+
+    fn enable(&self);
+
+    fn set_periodic(&self, frequency: u32);
+
+    fn set_one_shot(&self, nanoseconds: u64);
+}
+
+static DEVICES: [&dyn Device; 3] = [&apic::ClockEvent, &hpet::ClockEvent, &pit::ClockEvent];
+
+static DEVICE: AtomicUsize = AtomicUsize::new(usize::MAX);
+static TICKS: AtomicU64 = AtomicU64::new(0);
+
+static ONE_SHOT: [AtomicBool; MAX_CPUS] = [const { AtomicBool::new(false) }; MAX_CPUS];
+static LAST: [AtomicU64; MAX_CPUS] = [const { AtomicU64::new(0) }; MAX_CPUS];
+static NEXT: [AtomicU64; MAX_CPUS] = [const { AtomicU64::new(u64::MAX) }; MAX_CPUS];
+static SLICE_END: [AtomicU64; MAX_CPUS] = [const { AtomicU64::new(u64::MAX) }; MAX_CPUS];
+
+pub struct Jiffies;
+
+impl clock::Source for Jiffies {
+    fn name(&self) -> &'static str {
+        "jiffies"
+    }
+
+    fn rating(&self) -> Option<u16> {
+        Some(1)
+    }
+
+    fn frequency(&self) -> u64 {
+        u64::from(FREQUENCY)
+    }
+
+    fn read(&self) -> u64 {
+        TICKS.load(Relaxed)
+    }
+}
+
+fn device() -> Option<&'static dyn Device> {
+    DEVICES.get(DEVICE.load(Relaxed)).copied()
+}
+
+fn local_device(cpu: usize) -> Option<&'static dyn Device> {
+    if cpu == 0 {
+        device()
+    } else {
+        Some(&apic::ClockEvent)
+    }
+}
+
+fn start(cpu: usize, device: &dyn Device) -> bool {
+    let one_shot = device.is_one_shot_capable() && !clock::is_tick_based();
+    let now = clock::nanoseconds();
+
+    LAST[cpu].store(now - now % PERIOD, Relaxed);
+    ONE_SHOT[cpu].store(one_shot, Relaxed);
+
+    device.enable();
+
+    if one_shot {
+        rearm();
+    } else {
+        device.set_periodic(FREQUENCY);
+    }
+
+    one_shot
+}
+
+pub fn event() {
+    let cpu = smp::id();
+
+    let ticks = if ONE_SHOT[cpu].load(Relaxed) {
+        let last = LAST[cpu].load(Relaxed);
+        let ticks = clock::nanoseconds().saturating_sub(last) / PERIOD;
+        LAST[cpu].store(last + ticks * PERIOD, Relaxed);
+        ticks
+    } else {
+        1
+    };
+
+    if cpu == 0 && ticks > 0 {
+        TICKS.fetch_add(ticks, Relaxed);
+        softirq::raise(Softirq::Timer);
+    }
+
+    thread::tick(ticks);
+    rearm();
+}
+
+pub fn rearm() {
+    let cpu = smp::id();
+    let Some(device) = local_device(cpu).filter(|_| ONE_SHOT[cpu].load(Relaxed)) else {
+        return;
+    };
+
+    NEXT[cpu].store(u64::MAX, SeqCst);
+
+    let last = LAST[cpu].load(Relaxed);
+    let timer = (cpu == 0).then(timer::next_deadline).flatten();
+    let deadline = (last + IDLE_TICKS * PERIOD)
+        .min(SLICE_END[cpu].load(Relaxed))
+        .min(timer.unwrap_or(u64::MAX));
+
+    let ticks = deadline.saturating_sub(last).div_ceil(PERIOD).max(1);
+    let deadline = last + ticks * PERIOD;
+
+    NEXT[cpu].store(deadline, SeqCst);
+    device.set_one_shot(deadline.saturating_sub(clock::nanoseconds()).max(1));
+}
+
+pub fn set_slice(now: u64, slice: Option<u32>) {
+    let cpu = smp::id();
+    let end = slice.map_or(u64::MAX, |slice| {
+        now.saturating_add(u64::from(slice) * PERIOD)
+    });
+
+    SLICE_END[cpu].store(end, Relaxed);
+    rearm();
+}
+
+pub fn update(deadline: u64) {
+    if !ONE_SHOT[0].load(Relaxed) || deadline >= NEXT[0].load(SeqCst) {
+        return;
+    }
+
+    if smp::id() == 0 {
+        rearm();
+    } else {
+        smp::send_reschedule(0);
+    }
+}
+
+pub fn init() {
+    let (index, device, rating) = DEVICES
+        .iter()
+        .enumerate()
+        .filter_map(|(index, device)| Some((index, *device, device.rating()?)))
+        .max_by_key(|&(_, _, rating)| rating)
+        .expect("Failed to find a clock-event device.");
+
+    DEVICE.store(index, Relaxed);
+
+    let one_shot = start(0, device);
+
+    info!(
+        "Initialized the {} clock-event device (rating {rating}) in {} mode.",
+        device.name(),
+        if one_shot { "one-shot" } else { "periodic" }
+    );
+}
+
+pub fn init_secondary() {
+    let device = &apic::ClockEvent;
+
+    if device.rating().is_none() {
+        warn!("Failed to find a per-processor clock-event device.");
+        return;
+    }
+
+    start(smp::id(), device);
+}
@@ -0,0 +1,51 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::sync::atomic::AtomicU64;
+use core::sync::atomic::Ordering::Relaxed;
+use utility::time::{DateTime, NANOSECONDS_PER_SECOND};
+
+use crate::clock;
+
+static BOOT: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SystemTime {
+    nanoseconds: u64,
+}
+
+impl SystemTime {
+    pub fn now() -> Self {
+        Self {
+            nanoseconds: BOOT.load(Relaxed) + clock::nanoseconds(),
+        }
+    }
+
+    pub fn date_time(self) -> DateTime {
+        let nanosecond = u32::try_from(self.nanoseconds % NANOSECONDS_PER_SECOND)
+            .expect("Failed to convert nanoseconds.");
+
+        DateTime::from_unix(self.nanoseconds / NANOSECONDS_PER_SECOND, nanosecond)
+    }
+}
+
+pub fn set(date_time: DateTime) {
+    let nanoseconds = date_time
+        .unix_seconds()
+        .saturating_mul(NANOSECONDS_PER_SECOND);
+
+    BOOT.store(nanoseconds.saturating_sub(clock::nanoseconds()), Relaxed);
+}
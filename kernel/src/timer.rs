@@ -0,0 +1,127 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::time::Duration;
+use utility::lock::Spinlock;
+use utility::wheel::{Error, Handle, Wheel};
+
+use crate::{clock, interrupt, thread, tick};
+
+const CAPACITY: usize = 256;
+
+static WHEEL: Spinlock<Wheel<Callback, CAPACITY>> = Spinlock::new(Wheel::new());
+
+#[derive(Clone, Copy)]
+pub struct Callback {
+    function: fn(usize),
+    argument: usize,
+}
+
+impl Callback {
+    pub const fn new(function: fn(usize), argument: usize) -> Self {
+        Self { function, argument }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Elapsed;
+
+pub struct Timer {
+    handle: Handle,
+}
+
+impl Timer {
+    pub fn schedule(deadline: u64, callback: Callback) -> Result<Self, Error> {
+        let tick = deadline.div_ceil(tick::PERIOD);
+        let handle = interrupt::without_interrupts(|| {
+            let handle = WHEEL.lock().insert(tick, callback)?;
+            tick::update(tick.saturating_mul(tick::PERIOD));
+            Ok(handle)
+        })?;
+
+        Ok(Self { handle })
+    }
+
+    pub fn cancel(self) -> bool {
+        interrupt::without_interrupts(|| WHEEL.lock().cancel(self.handle)).is_some()
+    }
+}
+
+pub fn deadline(duration: Duration) -> u64 {
+    let duration = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+
+    clock::nanoseconds().saturating_add(duration)
+}
+
+pub fn next_deadline() -> Option<u64> {
+    let tick = interrupt::without_interrupts(|| WHEEL.lock().next_deadline())?;
+
+    Some(tick.saturating_mul(tick::PERIOD))
+}
+
+pub fn run() {
+    let now = clock::nanoseconds() / tick::PERIOD;
+
+    while let Some(callback) = interrupt::without_interrupts(|| WHEEL.lock().poll(now)) {
+        (callback.function)(callback.argument);
+    }
+}
+
+pub fn sleep(duration: Duration) {
+    assert!(
+        !interrupt::is_active(),
+        "Failed to sleep in interrupt context."
+    );
+
+    let deadline = deadline(duration);
+    let callback = Callback::new(thread::wake, thread::current());
+
+    while clock::nanoseconds() < deadline {
+        interrupt::without_interrupts(|| {
+            let timer =
+                Timer::schedule(deadline, callback).expect("Failed to schedule the sleep timer.");
+            thread::sleep();
+            timer.cancel();
+        });
+    }
+}
+
+pub fn timeout<T>(duration: Duration, mut poll: impl FnMut() -> Option<T>) -> Result<T, Elapsed> {
+    assert!(
+        !interrupt::is_active(),
+        "Failed to wait in interrupt context."
+    );
+
+    let deadline = deadline(duration);
+    let callback = Callback::new(thread::wake, thread::current());
+
+    loop {
+        if let Some(value) = poll() {
+            return Ok(value);
+        }
+
+        if clock::nanoseconds() >= deadline {
+            return Err(Elapsed);
+        }
+
+        interrupt::without_interrupts(|| {
+            let timer =
+                Timer::schedule(deadline, callback).expect("Failed to schedule the timeout timer.");
+            thread::sleep();
+            timer.cancel();
+        });
+    }
+}
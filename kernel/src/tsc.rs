@@ -0,0 +1,75 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use architecture::x86_64::{cpuid, instruction};
+use core::sync::atomic::Ordering::Relaxed;
+use core::sync::atomic::{AtomicBool, AtomicU64};
+use utility::{info, warn};
+
+use crate::{clock, pit};
+
+static FREQUENCY: AtomicU64 = AtomicU64::new(0);
+
+static RDTSCP: AtomicBool = AtomicBool::new(false);
+
+pub struct ClockSource;
+
+impl clock::Source for ClockSource {
+    fn name(&self) -> &'static str {
+        "TSC"
+    }
+
+    fn rating(&self) -> Option<u16> {
+        match FREQUENCY.load(Relaxed) {
+            0 => None,
+            _ if cpuid::has_invariant_tsc() => Some(300),
+            _ => Some(100),
+        }
+    }
+
+    fn frequency(&self) -> u64 {
+        FREQUENCY.load(Relaxed)
+    }
+
+    fn read(&self) -> u64 {
+        read()
+    }
+}
+
+pub fn read() -> u64 {
+    if RDTSCP.load(Relaxed) {
+        instruction::rdtscp().0
+    } else {
+        instruction::rdtsc()
+    }
+}
+
+pub fn init() {
+    RDTSCP.store(cpuid::has_rdtscp(), Relaxed);
+
+    let frequency = pit::calibrate(instruction::rdtsc);
+    FREQUENCY.store(frequency, Relaxed);
+
+    if !cpuid::has_invariant_tsc() {
+        warn!("The time stamp counter is not invariant.");
+    }
+
+    info!(
+        "Initialized the time stamp counter at {}.{:03} MHz.",
+        frequency / 1_000_000,
+        frequency / 1_000 % 1_000
+    );
+}
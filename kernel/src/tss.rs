@@ -14,17 +14,55 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use architecture::x86_64::paging::PAGE_SIZE;
 use architecture::x86_64::tss::Segment;
-use core::cell::LazyCell;
 use utility::info;
-use utility::lock::Spinlock;
+use utility::lock::{Lazy, RwSpinlock};
 
-use crate::gdt::GDT;
+use crate::smp::MAX_CPUS;
+use crate::{gdt, memory};
 
-pub static TSS: Spinlock<LazyCell<Segment>> = Spinlock::new(LazyCell::new(Segment::new));
+pub const DOUBLE_FAULT_STACK: u8 = 1;
+pub const NON_MASKABLE_INTERRUPT_STACK: u8 = 2;
+pub const MACHINE_CHECK_STACK: u8 = 3;
+pub const DEBUG_STACK: u8 = 4;
+
+const STACK_PAGES: u64 = 4;
+
+static TSS: [Lazy<RwSpinlock<Segment>>; MAX_CPUS] =
+    [const { Lazy::new(|| RwSpinlock::new(Segment::new())) }; MAX_CPUS];
+
+const STACKS: [u8; 4] = [
+    DOUBLE_FAULT_STACK,
+    NON_MASKABLE_INTERRUPT_STACK,
+    MACHINE_CHECK_STACK,
+    DEBUG_STACK,
+];
+
+pub fn segment(cpu: usize) -> *const Segment {
+    &raw const *TSS[cpu].read()
+}
+
+pub fn load(cpu: usize) {
+    for index in STACKS {
+        let top = memory::allocate_stack(STACK_PAGES);
+        TSS[cpu].write().set_interrupt_stack(index, top);
+    }
+
+    Segment::load(gdt::selector(5));
+}
+
+pub fn is_interrupt_stack(cpu: usize, address: u64) -> bool {
+    let segment = TSS[cpu].read();
+
+    STACKS.iter().any(|&index| {
+        let top = segment.interrupt_stack(index);
+        (top.saturating_sub(STACK_PAGES * PAGE_SIZE)..top).contains(&address)
+    })
+}
 
 pub fn init() {
-    Segment::load(GDT.lock().selector(5));
+    load(0);
 
     info!("Initialized the task state segment.");
 }
@@ -0,0 +1,125 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering::{AcqRel, Acquire};
+use utility::info;
+use utility::lock::Spinlock;
+use utility::ring::Ring;
+
+use crate::interrupt;
+use crate::thread;
+
+const MAX_QUEUES: usize = 8;
+
+const CAPACITY: usize = 64;
+
+pub const SYSTEM: Queue = Queue(0);
+
+static QUEUES: [Spinlock<Slot>; MAX_QUEUES] = [const { Spinlock::new(Slot::new()) }; MAX_QUEUES];
+
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    Full,
+    Limit,
+    Thread(thread::Error),
+}
+
+#[derive(Clone, Copy)]
+pub struct Work {
+    function: fn(usize),
+    argument: usize,
+}
+
+impl Work {
+    pub const fn new(function: fn(usize), argument: usize) -> Self {
+        Self { function, argument }
+    }
+}
+
+struct Slot {
+    name: &'static str,
+    items: Ring<Work, CAPACITY>,
+    worker: Option<usize>,
+}
+
+impl Slot {
+    const fn new() -> Self {
+        Self {
+            name: "",
+            items: Ring::new(),
+            worker: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Queue(usize);
+
+impl Queue {
+    pub fn name(self) -> &'static str {
+        interrupt::without_interrupts(|| QUEUES[self.0].lock().name)
+    }
+
+    pub fn queue(self, work: Work) -> Result<(), Error> {
+        let worker = interrupt::without_interrupts(|| {
+            let mut slot = QUEUES[self.0].lock();
+            slot.items.push(work).map_err(|_| Error::Full)?;
+
+            Ok(slot.worker)
+        })?;
+
+        if let Some(worker) = worker {
+            thread::wake(worker);
+        }
+
+        Ok(())
+    }
+}
+
+fn worker(index: usize) -> usize {
+    loop {
+        match interrupt::without_interrupts(|| QUEUES[index].lock().items.pop()) {
+            Some(work) => (work.function)(work.argument),
+            None => thread::sleep(),
+        }
+    }
+}
+
+pub fn create(name: &'static str) -> Result<Queue, Error> {
+    let index = COUNT
+        .fetch_update(AcqRel, Acquire, |count| {
+            (count < MAX_QUEUES).then_some(count + 1)
+        })
+        .map_err(|_| Error::Limit)?;
+
+    interrupt::without_interrupts(|| QUEUES[index].lock().name = name);
+
+    let handle = thread::spawn(name, worker, index).map_err(Error::Thread)?;
+    interrupt::without_interrupts(|| QUEUES[index].lock().worker = Some(handle.id()));
+    thread::wake(handle.id());
+
+    Ok(Queue(index))
+}
+
+pub fn init() {
+    let queue = create("events").expect("Failed to create the system workqueue.");
+    assert!(queue == SYSTEM, "Failed to reserve the system workqueue.");
+
+    info!("Initialized the {} workqueue.", SYSTEM.name());
+}
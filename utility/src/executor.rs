@@ -0,0 +1,505 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::cell::UnsafeCell;
+use core::future::Future;
+use core::mem::{self, MaybeUninit};
+use core::pin::Pin;
+use core::ptr;
+use core::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed, Release};
+use core::sync::atomic::{AtomicBool, AtomicPtr, AtomicU8, AtomicU32, AtomicU64, AtomicUsize};
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+use crate::lock::Spinlock;
+
+pub const ALIGNMENT: usize = 16;
+
+const FREE: u8 = 0;
+
+const RESERVED: u8 = 1;
+
+const ACTIVE: u8 = 2;
+
+const IDLE: usize = usize::MAX;
+
+static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake, release);
+
+type PollFn = unsafe fn(*mut u8, &mut Context<'_>) -> Poll<()>;
+
+type DropFn = unsafe fn(*mut u8);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    Full,
+    TooLarge,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Handle {
+    index: usize,
+    generation: u32,
+}
+
+impl Handle {
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    #[must_use]
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+struct Signal {
+    ready: AtomicU64,
+    notify: fn(),
+}
+
+struct Header {
+    index: AtomicUsize,
+    signal: AtomicPtr<Signal>,
+}
+
+#[repr(C, align(16))]
+struct Storage<const SIZE: usize>([MaybeUninit<u8>; SIZE]);
+
+struct Slot<const SIZE: usize> {
+    header: Header,
+    state: AtomicU8,
+    generation: AtomicU32,
+    poll: UnsafeCell<Option<PollFn>>,
+    drop: UnsafeCell<Option<DropFn>>,
+    storage: UnsafeCell<Storage<SIZE>>,
+}
+
+impl<const SIZE: usize> Slot<SIZE> {
+    const fn new() -> Self {
+        Self {
+            header: Header {
+                index: AtomicUsize::new(0),
+                signal: AtomicPtr::new(ptr::null_mut()),
+            },
+            state: AtomicU8::new(FREE),
+            generation: AtomicU32::new(0),
+            poll: UnsafeCell::new(None),
+            drop: UnsafeCell::new(None),
+            storage: UnsafeCell::new(Storage([MaybeUninit::uninit(); SIZE])),
+        }
+    }
+
+    fn future(&self) -> *mut u8 {
+        self.storage.get().cast()
+    }
+
+    fn waker(&self) -> Waker {
+        let data = ptr::from_ref(&self.header).cast();
+
+        unsafe { Waker::from_raw(RawWaker::new(data, &VTABLE)) }
+    }
+}
+
+pub struct Executor<const CAPACITY: usize, const SIZE: usize> {
+    signal: Signal,
+    running: AtomicBool,
+    current: AtomicUsize,
+    slots: [Slot<SIZE>; CAPACITY],
+}
+
+unsafe impl<const CAPACITY: usize, const SIZE: usize> Sync for Executor<CAPACITY, SIZE> {}
+
+impl<const CAPACITY: usize, const SIZE: usize> Executor<CAPACITY, SIZE> {
+    /// # Panics
+    ///
+    /// Panics if `CAPACITY` exceeds 64.
+    #[must_use]
+    pub const fn new(notify: fn()) -> Self {
+        assert!(CAPACITY <= 64, "Failed to fit the tasks in the ready set.");
+
+        Self {
+            signal: Signal {
+                ready: AtomicU64::new(0),
+                notify,
+            },
+            running: AtomicBool::new(false),
+            current: AtomicUsize::new(IDLE),
+            slots: [const { Slot::new() }; CAPACITY],
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if every slot is taken or the future does not fit in one.
+    pub fn spawn<F>(&'static self, future: F) -> Result<Handle, Error>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        if mem::size_of::<F>() > SIZE || mem::align_of::<F>() > ALIGNMENT {
+            return Err(Error::TooLarge);
+        }
+
+        let index = (0..CAPACITY)
+            .find(|&index| {
+                self.slots[index]
+                    .state
+                    .compare_exchange(FREE, RESERVED, Acquire, Relaxed)
+                    .is_ok()
+            })
+            .ok_or(Error::Full)?;
+
+        let slot = &self.slots[index];
+
+        unsafe {
+            slot.future().cast::<F>().write(future);
+            *slot.poll.get() = Some(poll::<F>);
+            *slot.drop.get() = Some(drop::<F>);
+        }
+
+        slot.header.index.store(index, Relaxed);
+        slot.header
+            .signal
+            .store(ptr::from_ref(&self.signal).cast_mut(), Release);
+        slot.state.store(ACTIVE, Release);
+        slot.waker().wake();
+
+        Ok(Handle {
+            index,
+            generation: slot.generation.load(Relaxed),
+        })
+    }
+
+    /// # Panics
+    ///
+    /// Panics if a ready task was never fully spawned.
+    pub fn run(&self) -> usize {
+        if self.running.swap(true, Acquire) {
+            return 0;
+        }
+
+        let mut ready = self.signal.ready.swap(0, AcqRel);
+        let mut polled = 0;
+
+        while ready != 0 {
+            let index = ready.trailing_zeros() as usize;
+            ready &= ready - 1;
+
+            let slot = &self.slots[index];
+            if slot.state.load(Acquire) != ACTIVE {
+                continue;
+            }
+
+            self.current.store(index, Relaxed);
+
+            let waker = slot.waker();
+            let mut context = Context::from_waker(&waker);
+            let (poll, drop) = unsafe { (*slot.poll.get(), *slot.drop.get()) };
+            let poll = poll.expect("Failed to find the poll function of a task.");
+
+            if unsafe { poll(slot.future(), &mut context) }.is_ready() {
+                let drop = drop.expect("Failed to find the drop function of a task.");
+                unsafe { drop(slot.future()) };
+
+                slot.generation.fetch_add(1, Relaxed);
+                slot.state.store(FREE, Release);
+            }
+
+            polled += 1;
+        }
+
+        self.current.store(IDLE, Relaxed);
+        self.running.store(false, Release);
+
+        polled
+    }
+
+    #[must_use]
+    pub fn current(&self) -> Option<Handle> {
+        let index = self.current.load(Relaxed);
+        if index == IDLE {
+            return None;
+        }
+
+        Some(Handle {
+            index,
+            generation: self.slots[index].generation.load(Relaxed),
+        })
+    }
+
+    #[must_use]
+    pub fn is_idle(&self) -> bool {
+        self.signal.ready.load(Acquire) == 0
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.slots
+            .iter()
+            .filter(|slot| slot.state.load(Acquire) == ACTIVE)
+            .count()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<const CAPACITY: usize, const SIZE: usize> Drop for Executor<CAPACITY, SIZE> {
+    fn drop(&mut self) {
+        for slot in &self.slots {
+            let drop = unsafe { *slot.drop.get() }.filter(|_| slot.state.load(Acquire) == ACTIVE);
+
+            if let Some(drop) = drop {
+                unsafe { drop(slot.future()) };
+            }
+        }
+    }
+}
+
+pub struct Local<T, const CAPACITY: usize> {
+    values: [Spinlock<Option<(u32, T)>>; CAPACITY],
+    initial: fn() -> T,
+}
+
+impl<T, const CAPACITY: usize> Local<T, CAPACITY> {
+    #[must_use]
+    pub const fn new(initial: fn() -> T) -> Self {
+        Self {
+            values: [const { Spinlock::new(None) }; CAPACITY],
+            initial,
+        }
+    }
+
+    pub fn with<R>(&self, handle: Handle, function: impl FnOnce(&mut T) -> R) -> R {
+        let mut entry = self.values[handle.index].lock();
+
+        let stale = !matches!(*entry, Some((generation, _)) if generation == handle.generation);
+        if stale {
+            *entry = None;
+        }
+
+        let (_, value) = entry.get_or_insert_with(|| (handle.generation, (self.initial)()));
+
+        function(value)
+    }
+}
+
+pub struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+
+        self.yielded = true;
+        context.waker().wake_by_ref();
+
+        Poll::Pending
+    }
+}
+
+#[must_use]
+pub fn yield_now() -> YieldNow {
+    YieldNow { yielded: false }
+}
+
+unsafe fn poll<F: Future<Output = ()>>(future: *mut u8, context: &mut Context<'_>) -> Poll<()> {
+    unsafe { Pin::new_unchecked(&mut *future.cast::<F>()) }.poll(context)
+}
+
+unsafe fn drop<F>(future: *mut u8) {
+    unsafe { ptr::drop_in_place(future.cast::<F>()) };
+}
+
+unsafe fn clone(data: *const ()) -> RawWaker {
+    RawWaker::new(data, &VTABLE)
+}
+
+unsafe fn wake(data: *const ()) {
+    let header = unsafe { &*data.cast::<Header>() };
+    let signal = header.signal.load(Acquire);
+
+    if let Some(signal) = unsafe { signal.as_ref() } {
+        signal
+            .ready
+            .fetch_or(1 << header.index.load(Relaxed), Release);
+        (signal.notify)();
+    }
+}
+
+unsafe fn release(_: *const ()) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::future;
+
+    fn ignore() {}
+
+    #[test]
+    fn test_run_to_completion() {
+        static EXECUTOR: Executor<4, 64> = Executor::new(ignore);
+        static DONE: AtomicBool = AtomicBool::new(false);
+
+        let handle = EXECUTOR
+            .spawn(async { DONE.store(true, Relaxed) })
+            .expect("Failed to spawn a task.");
+
+        assert_eq!(handle.index(), 0);
+        assert_eq!(EXECUTOR.len(), 1);
+        assert!(!EXECUTOR.is_idle());
+        assert_eq!(EXECUTOR.run(), 1);
+        assert!(DONE.load(Relaxed));
+        assert!(EXECUTOR.is_empty());
+        assert!(EXECUTOR.is_idle());
+        assert_eq!(EXECUTOR.run(), 0);
+    }
+
+    #[test]
+    fn test_yield_now() {
+        static EXECUTOR: Executor<4, 64> = Executor::new(ignore);
+        static STEPS: AtomicUsize = AtomicUsize::new(0);
+
+        EXECUTOR
+            .spawn(async {
+                for _ in 0..3 {
+                    STEPS.fetch_add(1, Relaxed);
+                    yield_now().await;
+                }
+            })
+            .expect("Failed to spawn a task.");
+
+        for step in 1..=3 {
+            assert_eq!(EXECUTOR.run(), 1);
+            assert_eq!(STEPS.load(Relaxed), step);
+        }
+
+        assert_eq!(EXECUTOR.run(), 1);
+        assert!(EXECUTOR.is_empty());
+    }
+
+    #[test]
+    fn test_external_wake() {
+        static EXECUTOR: Executor<4, 64> = Executor::new(notify);
+        static NOTIFIED: AtomicUsize = AtomicUsize::new(0);
+        static WAKER: Spinlock<Option<Waker>> = Spinlock::new(None);
+        static FLAG: AtomicBool = AtomicBool::new(false);
+
+        fn notify() {
+            NOTIFIED.fetch_add(1, Relaxed);
+        }
+
+        EXECUTOR
+            .spawn(future::poll_fn(|context| {
+                if FLAG.load(Acquire) {
+                    return Poll::Ready(());
+                }
+
+                *WAKER.lock() = Some(context.waker().clone());
+                Poll::Pending
+            }))
+            .expect("Failed to spawn a task.");
+
+        assert_eq!(NOTIFIED.load(Relaxed), 1);
+        assert_eq!(EXECUTOR.run(), 1);
+        assert!(EXECUTOR.is_idle());
+        assert_eq!(EXECUTOR.run(), 0);
+
+        FLAG.store(true, Release);
+        WAKER
+            .lock()
+            .take()
+            .expect("Failed to register a waker.")
+            .wake();
+
+        assert_eq!(NOTIFIED.load(Relaxed), 2);
+        assert_eq!(EXECUTOR.run(), 1);
+        assert!(EXECUTOR.is_empty());
+    }
+
+    #[test]
+    fn test_full() {
+        static EXECUTOR: Executor<2, 64> = Executor::new(ignore);
+
+        assert!(EXECUTOR.spawn(future::pending()).is_ok());
+        assert!(EXECUTOR.spawn(future::pending()).is_ok());
+        assert_eq!(EXECUTOR.spawn(future::pending()), Err(Error::Full));
+    }
+
+    #[test]
+    fn test_too_large() {
+        static EXECUTOR: Executor<2, 8> = Executor::new(ignore);
+
+        let buffer = [0u8; 64];
+        let result = EXECUTOR.spawn(async move {
+            assert_eq!(buffer.len(), 64);
+        });
+
+        assert_eq!(result, Err(Error::TooLarge));
+        assert!(EXECUTOR.is_empty());
+    }
+
+    #[test]
+    fn test_reuse() {
+        static EXECUTOR: Executor<1, 64> = Executor::new(ignore);
+
+        let first = EXECUTOR.spawn(async {}).expect("Failed to spawn a task.");
+        assert_eq!(EXECUTOR.run(), 1);
+
+        let second = EXECUTOR.spawn(async {}).expect("Failed to spawn a task.");
+        assert_eq!(first.index(), second.index());
+        assert_ne!(first.generation(), second.generation());
+    }
+
+    #[test]
+    fn test_task_local() {
+        static EXECUTOR: Executor<2, 64> = Executor::new(ignore);
+        static COUNTER: Local<usize, 2> = Local::new(|| 0);
+        static SEEN: AtomicUsize = AtomicUsize::new(0);
+
+        for _ in 0..2 {
+            EXECUTOR
+                .spawn(async {
+                    for _ in 0..3 {
+                        let handle = EXECUTOR
+                            .current()
+                            .expect("Failed to find the current task.");
+                        COUNTER.with(handle, |counter| *counter += 1);
+                        yield_now().await;
+                    }
+
+                    let handle = EXECUTOR
+                        .current()
+                        .expect("Failed to find the current task.");
+                    SEEN.fetch_add(COUNTER.with(handle, |counter| *counter), Relaxed);
+                })
+                .expect("Failed to spawn a task.");
+        }
+
+        while !EXECUTOR.is_empty() {
+            EXECUTOR.run();
+        }
+
+        assert_eq!(SEEN.load(Relaxed), 6);
+        assert_eq!(EXECUTOR.current(), None);
+    }
+}
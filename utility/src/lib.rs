@@ -15,8 +15,12 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 #![no_std]
-#![feature(lazy_get)]
 #![warn(clippy::pedantic)]
 
+pub mod executor;
 pub mod lock;
 pub mod logging;
+pub mod queue;
+pub mod ring;
+pub mod time;
+pub mod wheel;
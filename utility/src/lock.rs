@@ -14,10 +14,65 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+#[cfg(test)]
+extern crate std;
+
 use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
 use core::ops::{Deref, DerefMut};
-use core::sync::atomic::AtomicBool;
-use core::sync::atomic::Ordering::{Acquire, Release};
+use core::ptr;
+use core::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed, Release};
+use core::sync::atomic::{AtomicBool, AtomicPtr, AtomicU8, AtomicU32, AtomicUsize};
+
+const UNOWNED: usize = usize::MAX;
+
+const WRITER: usize = 1;
+
+const PENDING: usize = 2;
+
+const READER: usize = 4;
+
+const INCOMPLETE: u8 = 0;
+
+const RUNNING: u8 = 1;
+
+const COMPLETE: u8 = 2;
+
+#[cfg(not(test))]
+static INTERRUPTS: AtomicPtr<Interrupts> = AtomicPtr::new(ptr::null_mut());
+
+#[cfg(test)]
+std::thread_local! {
+    static INTERRUPTS: core::cell::Cell<Option<&'static Interrupts>> = const {
+        core::cell::Cell::new(None)
+    };
+}
+
+pub struct Interrupts {
+    pub save: fn() -> u64,
+    pub restore: fn(u64),
+    pub cpu: fn() -> usize,
+}
+
+#[cfg(not(test))]
+pub fn set_interrupts(interrupts: &'static Interrupts) {
+    INTERRUPTS.store(ptr::from_ref(interrupts).cast_mut(), Release);
+}
+
+#[cfg(test)]
+pub fn set_interrupts(interrupts: &'static Interrupts) {
+    INTERRUPTS.with(|hooks| hooks.set(Some(interrupts)));
+}
+
+#[cfg(not(test))]
+fn interrupts() -> Option<&'static Interrupts> {
+    unsafe { INTERRUPTS.load(Acquire).as_ref() }
+}
+
+#[cfg(test)]
+fn interrupts() -> Option<&'static Interrupts> {
+    INTERRUPTS.with(core::cell::Cell::get)
+}
 
 pub struct Spinlock<T> {
     locked: AtomicBool,
@@ -47,6 +102,21 @@ impl<T> Spinlock<T> {
 
         Guard { lock: self }
     }
+
+    pub fn try_lock(&self) -> Option<Guard<'_, T>> {
+        if self.locked.swap(true, Acquire) {
+            return None;
+        }
+
+        Some(Guard { lock: self })
+    }
+
+    /// # Safety
+    ///
+    /// The lock must be held by a guard that was forgotten instead of dropped.
+    pub unsafe fn force_unlock(&self) {
+        self.locked.store(false, Release);
+    }
 }
 
 impl<T> Deref for Guard<'_, T> {
@@ -67,3 +137,965 @@ impl<T> Drop for Guard<'_, T> {
         self.lock.locked.store(false, Release);
     }
 }
+
+pub struct IrqSpinlock<T> {
+    locked: AtomicBool,
+    owner: AtomicUsize,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T> Sync for IrqSpinlock<T> where T: Send {}
+
+pub struct IrqGuard<'a, T> {
+    lock: &'a IrqSpinlock<T>,
+    flags: u64,
+}
+
+unsafe impl<T> Sync for IrqGuard<'_, T> where T: Sync {}
+
+impl<T> IrqSpinlock<T> {
+    pub const fn new(value: T) -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            owner: AtomicUsize::new(UNOWNED),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if this processor already holds the lock.
+    pub fn lock(&self) -> IrqGuard<'_, T> {
+        let interrupts = interrupts();
+        let flags = interrupts.map_or(0, |interrupts| (interrupts.save)());
+        let cpu = interrupts.map_or(UNOWNED, |interrupts| (interrupts.cpu)());
+
+        while self.locked.swap(true, Acquire) {
+            assert!(
+                cpu == UNOWNED || self.owner.load(Relaxed) != cpu,
+                "Failed to acquire a lock already held by this processor."
+            );
+
+            core::hint::spin_loop();
+        }
+
+        self.owner.store(cpu, Relaxed);
+
+        IrqGuard { lock: self, flags }
+    }
+
+    pub fn try_lock(&self) -> Option<IrqGuard<'_, T>> {
+        let interrupts = interrupts();
+        let flags = interrupts.map_or(0, |interrupts| (interrupts.save)());
+
+        if self.locked.swap(true, Acquire) {
+            if let Some(interrupts) = interrupts {
+                (interrupts.restore)(flags);
+            }
+
+            return None;
+        }
+
+        let cpu = interrupts.map_or(UNOWNED, |interrupts| (interrupts.cpu)());
+        self.owner.store(cpu, Relaxed);
+
+        Some(IrqGuard { lock: self, flags })
+    }
+
+    #[must_use]
+    pub fn is_locked(&self) -> bool {
+        self.locked.load(Relaxed)
+    }
+
+    /// # Safety
+    ///
+    /// The holder of the lock must never touch the protected value again.
+    pub unsafe fn force_unlock(&self) {
+        self.owner.store(UNOWNED, Relaxed);
+        self.locked.store(false, Release);
+    }
+}
+
+impl<T> Deref for IrqGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T> DerefMut for IrqGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
+
+impl<T> Drop for IrqGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.owner.store(UNOWNED, Relaxed);
+        self.lock.locked.store(false, Release);
+
+        if let Some(interrupts) = interrupts() {
+            (interrupts.restore)(self.flags);
+        }
+    }
+}
+
+pub struct TicketLock<T> {
+    next: AtomicU32,
+    serving: AtomicU32,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T> Sync for TicketLock<T> where T: Send {}
+
+pub struct TicketGuard<'a, T> {
+    lock: &'a TicketLock<T>,
+}
+
+unsafe impl<T> Sync for TicketGuard<'_, T> where T: Sync {}
+
+impl<T> TicketLock<T> {
+    pub const fn new(value: T) -> Self {
+        Self {
+            next: AtomicU32::new(0),
+            serving: AtomicU32::new(0),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    pub fn lock(&self) -> TicketGuard<'_, T> {
+        let ticket = self.next.fetch_add(1, Relaxed);
+
+        while self.serving.load(Acquire) != ticket {
+            core::hint::spin_loop();
+        }
+
+        TicketGuard { lock: self }
+    }
+
+    pub fn try_lock(&self) -> Option<TicketGuard<'_, T>> {
+        let serving = self.serving.load(Relaxed);
+
+        self.next
+            .compare_exchange(serving, serving.wrapping_add(1), Acquire, Relaxed)
+            .ok()
+            .map(|_| TicketGuard { lock: self })
+    }
+
+    #[must_use]
+    pub fn is_locked(&self) -> bool {
+        self.next.load(Relaxed) != self.serving.load(Relaxed)
+    }
+}
+
+impl<T> Deref for TicketGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T> DerefMut for TicketGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
+
+impl<T> Drop for TicketGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.serving.fetch_add(1, Release);
+    }
+}
+
+pub struct McsNode {
+    next: AtomicPtr<McsNode>,
+    locked: AtomicBool,
+}
+
+impl Default for McsNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl McsNode {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            next: AtomicPtr::new(ptr::null_mut()),
+            locked: AtomicBool::new(false),
+        }
+    }
+}
+
+pub struct McsLock<T> {
+    tail: AtomicPtr<McsNode>,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T> Sync for McsLock<T> where T: Send {}
+
+pub struct McsGuard<'a, T> {
+    lock: &'a McsLock<T>,
+    node: &'a McsNode,
+}
+
+unsafe impl<T> Sync for McsGuard<'_, T> where T: Sync {}
+
+impl<T> McsLock<T> {
+    pub const fn new(value: T) -> Self {
+        Self {
+            tail: AtomicPtr::new(ptr::null_mut()),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The returned guard must be dropped, not forgotten, before `node` is reused or freed.
+    pub unsafe fn lock<'a>(&'a self, node: &'a mut McsNode) -> McsGuard<'a, T> {
+        node.next = AtomicPtr::new(ptr::null_mut());
+        node.locked = AtomicBool::new(true);
+
+        let node = &*node;
+        let pointer = ptr::from_ref(node).cast_mut();
+        let previous = self.tail.swap(pointer, AcqRel);
+
+        if let Some(previous) = unsafe { previous.as_ref() } {
+            previous.next.store(pointer, Release);
+
+            while node.locked.load(Acquire) {
+                core::hint::spin_loop();
+            }
+        }
+
+        McsGuard { lock: self, node }
+    }
+
+    /// # Safety
+    ///
+    /// The returned guard must be dropped, not forgotten, before `node` is reused or freed.
+    pub unsafe fn try_lock<'a>(&'a self, node: &'a mut McsNode) -> Option<McsGuard<'a, T>> {
+        node.next = AtomicPtr::new(ptr::null_mut());
+        node.locked = AtomicBool::new(false);
+
+        let node = &*node;
+        let pointer = ptr::from_ref(node).cast_mut();
+
+        self.tail
+            .compare_exchange(ptr::null_mut(), pointer, Acquire, Relaxed)
+            .ok()
+            .map(|_| McsGuard { lock: self, node })
+    }
+
+    pub fn with<R>(&self, function: impl FnOnce(&mut T) -> R) -> R {
+        let mut node = McsNode::new();
+        let mut guard = unsafe { self.lock(&mut node) };
+
+        function(&mut guard)
+    }
+
+    #[must_use]
+    pub fn is_locked(&self) -> bool {
+        !self.tail.load(Relaxed).is_null()
+    }
+}
+
+impl<T> Deref for McsGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T> DerefMut for McsGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
+
+impl<T> Drop for McsGuard<'_, T> {
+    fn drop(&mut self) {
+        let pointer = ptr::from_ref(self.node).cast_mut();
+        let mut next = self.node.next.load(Acquire);
+
+        if next.is_null() {
+            if self
+                .lock
+                .tail
+                .compare_exchange(pointer, ptr::null_mut(), Release, Relaxed)
+                .is_ok()
+            {
+                return;
+            }
+
+            loop {
+                next = self.node.next.load(Acquire);
+                if !next.is_null() {
+                    break;
+                }
+
+                core::hint::spin_loop();
+            }
+        }
+
+        unsafe { (*next).locked.store(false, Release) };
+    }
+}
+
+pub struct RwSpinlock<T> {
+    state: AtomicUsize,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T> Sync for RwSpinlock<T> where T: Send + Sync {}
+
+pub struct ReadGuard<'a, T> {
+    lock: &'a RwSpinlock<T>,
+}
+
+unsafe impl<T> Sync for ReadGuard<'_, T> where T: Sync {}
+
+pub struct WriteGuard<'a, T> {
+    lock: &'a RwSpinlock<T>,
+}
+
+unsafe impl<T> Sync for WriteGuard<'_, T> where T: Sync {}
+
+impl<T> RwSpinlock<T> {
+    pub const fn new(value: T) -> Self {
+        Self {
+            state: AtomicUsize::new(0),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    pub fn read(&self) -> ReadGuard<'_, T> {
+        loop {
+            if let Some(guard) = self.try_read() {
+                return guard;
+            }
+
+            core::hint::spin_loop();
+        }
+    }
+
+    pub fn try_read(&self) -> Option<ReadGuard<'_, T>> {
+        self.state
+            .fetch_update(Acquire, Relaxed, |state| {
+                (state & (WRITER | PENDING) == 0).then_some(state + READER)
+            })
+            .ok()
+            .map(|_| ReadGuard { lock: self })
+    }
+
+    pub fn write(&self) -> WriteGuard<'_, T> {
+        loop {
+            let state = self.state.load(Relaxed);
+
+            if state & !PENDING == 0 {
+                if self
+                    .state
+                    .compare_exchange_weak(state, WRITER, Acquire, Relaxed)
+                    .is_ok()
+                {
+                    return WriteGuard { lock: self };
+                }
+            } else if state & PENDING == 0 {
+                self.state.fetch_or(PENDING, Relaxed);
+            }
+
+            core::hint::spin_loop();
+        }
+    }
+
+    pub fn try_write(&self) -> Option<WriteGuard<'_, T>> {
+        self.state
+            .fetch_update(Acquire, Relaxed, |state| {
+                (state & !PENDING == 0).then_some(WRITER)
+            })
+            .ok()
+            .map(|_| WriteGuard { lock: self })
+    }
+
+    #[must_use]
+    pub fn readers(&self) -> usize {
+        self.state.load(Relaxed) / READER
+    }
+
+    #[must_use]
+    pub fn is_write_locked(&self) -> bool {
+        self.state.load(Relaxed) & WRITER != 0
+    }
+}
+
+impl<T> Deref for ReadGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T> Drop for ReadGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.state.fetch_sub(READER, Release);
+    }
+}
+
+impl<T> Deref for WriteGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T> DerefMut for WriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
+
+impl<T> Drop for WriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.state.fetch_and(!WRITER, Release);
+    }
+}
+
+pub struct Once {
+    state: AtomicU8,
+}
+
+impl Default for Once {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Once {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            state: AtomicU8::new(INCOMPLETE),
+        }
+    }
+
+    pub fn call_once(&self, function: impl FnOnce()) {
+        loop {
+            match self
+                .state
+                .compare_exchange(INCOMPLETE, RUNNING, Acquire, Acquire)
+            {
+                Ok(_) => {
+                    function();
+                    self.state.store(COMPLETE, Release);
+                    return;
+                }
+                Err(COMPLETE) => return,
+                Err(_) => core::hint::spin_loop(),
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn is_completed(&self) -> bool {
+        self.state.load(Acquire) == COMPLETE
+    }
+}
+
+pub struct OnceLock<T> {
+    once: Once,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+unsafe impl<T> Sync for OnceLock<T> where T: Send + Sync {}
+
+unsafe impl<T> Send for OnceLock<T> where T: Send {}
+
+impl<T> Default for OnceLock<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> OnceLock<T> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            once: Once::new(),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self) -> Option<&T> {
+        self.once
+            .is_completed()
+            .then(|| unsafe { (*self.value.get()).assume_init_ref() })
+    }
+
+    pub fn get_or_init(&self, function: impl FnOnce() -> T) -> &T {
+        self.once.call_once(|| unsafe {
+            (*self.value.get()).write(function());
+        });
+
+        unsafe { (*self.value.get()).assume_init_ref() }
+    }
+
+    /// # Errors
+    ///
+    /// Returns the value back if the cell is already initialized.
+    pub fn set(&self, value: T) -> Result<(), T> {
+        let mut value = Some(value);
+
+        self.once.call_once(|| {
+            if let Some(value) = value.take() {
+                unsafe { (*self.value.get()).write(value) };
+            }
+        });
+
+        value.map_or(Ok(()), Err)
+    }
+}
+
+impl<T> Drop for OnceLock<T> {
+    fn drop(&mut self) {
+        if self.once.is_completed() {
+            unsafe { self.value.get_mut().assume_init_drop() };
+        }
+    }
+}
+
+pub struct Lazy<T, F = fn() -> T> {
+    cell: OnceLock<T>,
+    initializer: F,
+}
+
+unsafe impl<T, F> Sync for Lazy<T, F>
+where
+    T: Send + Sync,
+    F: Sync,
+{
+}
+
+impl<T, F: Fn() -> T> Lazy<T, F> {
+    pub const fn new(initializer: F) -> Self {
+        Self {
+            cell: OnceLock::new(),
+            initializer,
+        }
+    }
+
+    pub fn force(this: &Self) -> &T {
+        this.cell.get_or_init(|| (this.initializer)())
+    }
+
+    #[must_use]
+    pub fn get(this: &Self) -> Option<&T> {
+        this.cell.get()
+    }
+}
+
+impl<T, F: Fn() -> T> Deref for Lazy<T, F> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        Lazy::force(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::cell::Cell;
+    use std::sync::atomic::AtomicUsize;
+    use std::thread_local;
+
+    const INTERRUPT_FLAG: u64 = 1 << 9;
+
+    const THREADS: usize = 4;
+
+    const ITERATIONS: usize = 500;
+
+    static NEXT_CPU: AtomicUsize = AtomicUsize::new(0);
+
+    static TEST_INTERRUPTS: Interrupts = Interrupts { save, restore, cpu };
+
+    thread_local! {
+        static FLAGS: Cell<u64> = const { Cell::new(INTERRUPT_FLAG) };
+        static CPU: usize = NEXT_CPU.fetch_add(1, Relaxed);
+    }
+
+    fn save() -> u64 {
+        FLAGS.with(|flags| flags.replace(flags.get() & !INTERRUPT_FLAG))
+    }
+
+    fn restore(saved: u64) {
+        FLAGS.with(|flags| flags.set(saved));
+    }
+
+    fn cpu() -> usize {
+        CPU.with(|cpu| *cpu)
+    }
+
+    fn enabled() -> bool {
+        FLAGS.with(|flags| flags.get() & INTERRUPT_FLAG != 0)
+    }
+
+    fn stress(increment: fn()) {
+        let threads: std::vec::Vec<_> = (0..THREADS)
+            .map(|_| {
+                std::thread::spawn(move || {
+                    for _ in 0..ITERATIONS {
+                        increment();
+                    }
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            thread.join().expect("Failed to join a thread.");
+        }
+    }
+
+    #[test]
+    fn test_spinlock() {
+        let lock = Spinlock::new(1);
+
+        *lock.lock() += 1;
+
+        assert_eq!(*lock.lock(), 2);
+    }
+
+    #[test]
+    fn test_spinlock_try_lock() {
+        let lock = Spinlock::new(());
+
+        let guard = lock.try_lock();
+        assert!(guard.is_some());
+        assert!(lock.try_lock().is_none());
+
+        drop(guard);
+        assert!(lock.try_lock().is_some());
+    }
+
+    #[test]
+    fn test_ticket_lock_try_lock() {
+        let lock = TicketLock::new(());
+
+        let guard = lock.try_lock();
+        assert!(guard.is_some());
+        assert!(lock.is_locked());
+        assert!(lock.try_lock().is_none());
+
+        drop(guard);
+        assert!(!lock.is_locked());
+        assert!(lock.try_lock().is_some());
+    }
+
+    #[test]
+    fn test_ticket_lock_order() {
+        static LOCK: TicketLock<std::vec::Vec<u32>> = TicketLock::new(std::vec::Vec::new());
+
+        let guard = LOCK.lock();
+        let threads: std::vec::Vec<_> = (0..4)
+            .map(|index| {
+                let thread = std::thread::spawn(move || LOCK.lock().push(index));
+
+                while LOCK.next.load(Acquire) != index + 2 {
+                    std::thread::yield_now();
+                }
+
+                thread
+            })
+            .collect();
+
+        drop(guard);
+
+        for thread in threads {
+            thread.join().expect("Failed to join a thread.");
+        }
+
+        assert_eq!(*LOCK.lock(), [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_ticket_lock_stress() {
+        static LOCK: TicketLock<usize> = TicketLock::new(0);
+
+        stress(|| *LOCK.lock() += 1);
+
+        assert_eq!(*LOCK.lock(), THREADS * ITERATIONS);
+    }
+
+    #[test]
+    fn test_mcs_lock_try_lock() {
+        let lock = McsLock::new(());
+        let mut first = McsNode::new();
+        let mut second = McsNode::new();
+
+        let guard = unsafe { lock.try_lock(&mut first) };
+        assert!(guard.is_some());
+        assert!(lock.is_locked());
+        assert!(unsafe { lock.try_lock(&mut second) }.is_none());
+
+        drop(guard);
+        assert!(!lock.is_locked());
+        assert!(unsafe { lock.try_lock(&mut second) }.is_some());
+    }
+
+    #[test]
+    fn test_mcs_lock_stress() {
+        static LOCK: McsLock<usize> = McsLock::new(0);
+
+        stress(|| LOCK.with(|value| *value += 1));
+
+        assert_eq!(LOCK.with(|value| *value), THREADS * ITERATIONS);
+        assert!(!LOCK.is_locked());
+    }
+
+    #[test]
+    fn test_spinlock_stress() {
+        static LOCK: Spinlock<usize> = Spinlock::new(0);
+
+        stress(|| *LOCK.lock() += 1);
+
+        assert_eq!(*LOCK.lock(), THREADS * ITERATIONS);
+    }
+
+    #[test]
+    fn test_rw_spinlock_readers() {
+        let lock = RwSpinlock::new(5);
+
+        let first = lock.read();
+        let second = lock.read();
+
+        assert_eq!(*first + *second, 10);
+        assert_eq!(lock.readers(), 2);
+        assert!(lock.try_write().is_none());
+
+        drop(first);
+        drop(second);
+
+        assert_eq!(lock.readers(), 0);
+        assert!(lock.try_write().is_some());
+    }
+
+    #[test]
+    fn test_rw_spinlock_writer() {
+        let lock = RwSpinlock::new(1);
+
+        {
+            let mut guard = lock.write();
+            *guard += 1;
+
+            assert!(lock.is_write_locked());
+            assert!(lock.try_read().is_none());
+            assert!(lock.try_write().is_none());
+        }
+
+        assert!(!lock.is_write_locked());
+        assert_eq!(*lock.read(), 2);
+    }
+
+    #[test]
+    fn test_rw_spinlock_pending_writer() {
+        static LOCK: RwSpinlock<usize> = RwSpinlock::new(0);
+
+        let reader = LOCK.read();
+        let writer = std::thread::spawn(|| *LOCK.write() += 1);
+
+        while LOCK.state.load(Acquire) & PENDING == 0 {
+            std::thread::yield_now();
+        }
+
+        assert!(LOCK.try_read().is_none());
+
+        drop(reader);
+        writer.join().expect("Failed to join a thread.");
+
+        assert_eq!(*LOCK.read(), 1);
+    }
+
+    #[test]
+    fn test_rw_spinlock_try_write_pending() {
+        let lock = RwSpinlock::new(());
+        lock.state.store(PENDING, Relaxed);
+
+        let writer = lock.try_write();
+        assert!(writer.is_some());
+        assert!(lock.try_read().is_none());
+
+        drop(writer);
+        assert!(lock.try_read().is_some());
+    }
+
+    #[test]
+    fn test_rw_spinlock_stress() {
+        static LOCK: RwSpinlock<(usize, usize)> = RwSpinlock::new((0, 0));
+
+        stress(|| {
+            {
+                let mut guard = LOCK.write();
+                guard.0 += 1;
+                guard.1 += 1;
+            }
+
+            let guard = LOCK.read();
+            assert_eq!(guard.0, guard.1);
+        });
+
+        assert_eq!(LOCK.read().0, THREADS * ITERATIONS);
+    }
+
+    #[test]
+    fn test_once() {
+        static ONCE: Once = Once::new();
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        assert!(!ONCE.is_completed());
+
+        stress(|| {
+            ONCE.call_once(|| {
+                CALLS.fetch_add(1, Relaxed);
+            });
+        });
+
+        assert!(ONCE.is_completed());
+        assert_eq!(CALLS.load(Relaxed), 1);
+    }
+
+    #[test]
+    fn test_once_lock() {
+        let cell = OnceLock::new();
+
+        assert_eq!(cell.get(), None);
+        assert_eq!(cell.set(1), Ok(()));
+        assert_eq!(cell.set(2), Err(2));
+        assert_eq!(cell.get_or_init(|| 3), &1);
+        assert_eq!(cell.get(), Some(&1));
+    }
+
+    #[test]
+    fn test_once_lock_drop() {
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        struct Counted;
+
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Relaxed);
+            }
+        }
+
+        drop(OnceLock::<Counted>::new());
+        assert_eq!(DROPS.load(Relaxed), 0);
+
+        let cell = OnceLock::new();
+        assert!(cell.set(Counted).is_ok());
+        drop(cell);
+        assert_eq!(DROPS.load(Relaxed), 1);
+    }
+
+    #[test]
+    fn test_lazy() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        static VALUE: Lazy<usize> = Lazy::new(|| {
+            CALLS.fetch_add(1, Relaxed);
+            42
+        });
+
+        assert_eq!(Lazy::get(&VALUE), None);
+
+        stress(|| assert_eq!(*VALUE, 42));
+
+        assert_eq!(Lazy::get(&VALUE), Some(&42));
+        assert_eq!(CALLS.load(Relaxed), 1);
+    }
+
+    #[test]
+    fn test_irq_spinlock_disables_interrupts() {
+        set_interrupts(&TEST_INTERRUPTS);
+        let lock = IrqSpinlock::new(0);
+
+        assert!(enabled());
+
+        {
+            let mut guard = lock.lock();
+            *guard += 1;
+
+            assert!(!enabled());
+            assert!(lock.is_locked());
+        }
+
+        assert!(enabled());
+        assert!(!lock.is_locked());
+        assert_eq!(*lock.lock(), 1);
+    }
+
+    #[test]
+    fn test_irq_spinlock_nested_restore() {
+        set_interrupts(&TEST_INTERRUPTS);
+        let outer = IrqSpinlock::new(());
+        let inner = IrqSpinlock::new(());
+
+        let first = outer.lock();
+        let second = inner.lock();
+
+        drop(second);
+        assert!(!enabled());
+
+        drop(first);
+        assert!(enabled());
+    }
+
+    #[test]
+    fn test_irq_spinlock_try_lock() {
+        set_interrupts(&TEST_INTERRUPTS);
+        let lock = IrqSpinlock::new(());
+
+        let guard = lock.try_lock();
+        assert!(guard.is_some());
+        assert!(!enabled());
+        assert!(lock.try_lock().is_none());
+        assert!(!enabled());
+
+        drop(guard);
+        assert!(enabled());
+        assert!(lock.try_lock().is_some());
+        assert!(enabled());
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to acquire a lock already held by this processor.")]
+    fn test_irq_spinlock_recursion() {
+        set_interrupts(&TEST_INTERRUPTS);
+        let lock = IrqSpinlock::new(());
+
+        let _first = lock.lock();
+        let _second = lock.lock();
+    }
+
+    #[test]
+    fn test_irq_spinlock_threads() {
+        static LOCK: IrqSpinlock<usize> = IrqSpinlock::new(0);
+
+        let threads: std::vec::Vec<_> = (0..4)
+            .map(|_| {
+                std::thread::spawn(|| {
+                    set_interrupts(&TEST_INTERRUPTS);
+
+                    for _ in 0..1000 {
+                        *LOCK.lock() += 1;
+                    }
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            thread.join().expect("Failed to join a thread.");
+        }
+
+        assert_eq!(*LOCK.lock(), 4000);
+    }
+}
@@ -14,10 +14,9 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use core::cell::LazyCell;
 use core::fmt::{Arguments, Error};
 
-use crate::lock::Spinlock;
+use crate::lock::OnceLock;
 
 pub enum Level {
     Debug,
@@ -31,17 +30,20 @@ pub trait Log: Sync + Send {
     fn handler(&self, level: Level, arguments: Arguments<'_>) -> Result<(), Error>;
 }
 
-static LOGGER: Spinlock<LazyCell<Option<&dyn Log>>> = Spinlock::new(LazyCell::new(|| None));
+static LOGGER: OnceLock<&dyn Log> = OnceLock::new();
 
+/// # Panics
+///
+/// Panics if a logger is already set.
 pub fn set_logger(logger: &'static dyn Log) {
-    let guard = &mut LOGGER.lock();
-    let global_logger = LazyCell::force_mut(guard);
-
-    *global_logger = Some(logger);
+    assert!(
+        LOGGER.set(logger).is_ok(),
+        "Failed to set the logger more than once."
+    );
 }
 
 pub fn get_logger() -> Option<&'static dyn Log> {
-    **LOGGER.lock()
+    LOGGER.get().copied()
 }
 
 #[macro_export]
@@ -0,0 +1,247 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+pub const LEVELS: usize = 8;
+
+#[derive(Clone, Copy)]
+struct Link {
+    level: usize,
+    queued: bool,
+    previous: Option<usize>,
+    next: Option<usize>,
+}
+
+impl Link {
+    const EMPTY: Self = Self {
+        level: 0,
+        queued: false,
+        previous: None,
+        next: None,
+    };
+}
+
+pub struct RunQueue<const CAPACITY: usize> {
+    links: [Link; CAPACITY],
+    heads: [Option<usize>; LEVELS],
+    tails: [Option<usize>; LEVELS],
+    occupied: u32,
+    length: usize,
+}
+
+impl<const CAPACITY: usize> Default for RunQueue<CAPACITY> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const CAPACITY: usize> RunQueue<CAPACITY> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            links: [Link::EMPTY; CAPACITY],
+            heads: [None; LEVELS],
+            tails: [None; LEVELS],
+            occupied: 0,
+            length: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    #[must_use]
+    pub fn contains(&self, index: usize) -> bool {
+        self.links[index].queued
+    }
+
+    #[must_use]
+    pub fn highest(&self) -> Option<usize> {
+        if self.occupied == 0 {
+            return None;
+        }
+
+        Some(self.occupied.trailing_zeros() as usize)
+    }
+
+    pub fn find(&self, mut predicate: impl FnMut(usize) -> bool) -> Option<usize> {
+        for level in 0..LEVELS {
+            let mut cursor = self.heads[level];
+
+            while let Some(index) = cursor {
+                if predicate(index) {
+                    return Some(index);
+                }
+
+                cursor = self.links[index].next;
+            }
+        }
+
+        None
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `level` is out of range or the entry is already queued.
+    pub fn push(&mut self, index: usize, level: usize) {
+        assert!(level < LEVELS, "Failed to validate the queue level.");
+        assert!(
+            !self.links[index].queued,
+            "Failed to queue an entry that is already queued."
+        );
+
+        let tail = self.tails[level];
+
+        self.links[index] = Link {
+            level,
+            queued: true,
+            previous: tail,
+            next: None,
+        };
+
+        match tail {
+            Some(tail) => self.links[tail].next = Some(index),
+            None => self.heads[level] = Some(index),
+        }
+
+        self.tails[level] = Some(index);
+        self.occupied |= 1 << level;
+        self.length += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<usize> {
+        let level = self.highest()?;
+        let index = self.heads[level]?;
+
+        self.remove(index);
+
+        Some(index)
+    }
+
+    pub fn remove(&mut self, index: usize) -> bool {
+        let link = self.links[index];
+        if !link.queued {
+            return false;
+        }
+
+        match link.previous {
+            Some(previous) => self.links[previous].next = link.next,
+            None => self.heads[link.level] = link.next,
+        }
+
+        match link.next {
+            Some(next) => self.links[next].previous = link.previous,
+            None => self.tails[link.level] = link.previous,
+        }
+
+        if self.heads[link.level].is_none() {
+            self.occupied &= !(1 << link.level);
+        }
+
+        self.links[index] = Link::EMPTY;
+        self.length -= 1;
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_in_first_out() {
+        let mut queue = RunQueue::<8>::new();
+
+        queue.push(3, 2);
+        queue.push(1, 2);
+        queue.push(5, 2);
+
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.pop(), Some(3));
+        assert_eq!(queue.pop(), Some(1));
+        assert_eq!(queue.pop(), Some(5));
+        assert_eq!(queue.pop(), None);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_priority_order() {
+        let mut queue = RunQueue::<8>::new();
+
+        queue.push(0, 7);
+        queue.push(1, 3);
+        queue.push(2, 0);
+        queue.push(3, 3);
+
+        assert_eq!(queue.highest(), Some(0));
+        assert_eq!(queue.pop(), Some(2));
+        assert_eq!(queue.highest(), Some(3));
+        assert_eq!(queue.pop(), Some(1));
+        assert_eq!(queue.pop(), Some(3));
+        assert_eq!(queue.pop(), Some(0));
+        assert_eq!(queue.highest(), None);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut queue = RunQueue::<8>::new();
+
+        queue.push(0, 1);
+        queue.push(1, 1);
+        queue.push(2, 1);
+
+        assert!(queue.remove(1));
+        assert!(!queue.remove(1));
+        assert!(!queue.contains(1));
+
+        assert!(queue.remove(2));
+        assert_eq!(queue.pop(), Some(0));
+        assert_eq!(queue.highest(), None);
+
+        queue.push(1, 4);
+        assert_eq!(queue.pop(), Some(1));
+    }
+
+    #[test]
+    fn test_find() {
+        let mut queue = RunQueue::<8>::new();
+
+        queue.push(4, 5);
+        queue.push(2, 1);
+        queue.push(6, 1);
+
+        assert_eq!(queue.find(|_| true), Some(2));
+        assert_eq!(queue.find(|index| index != 2), Some(6));
+        assert_eq!(queue.find(|index| index > 5 && index != 6), None);
+        assert_eq!(queue.len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to queue an entry that is already queued.")]
+    fn test_push_twice() {
+        let mut queue = RunQueue::<4>::new();
+
+        queue.push(0, 0);
+        queue.push(0, 1);
+    }
+}
@@ -0,0 +1,126 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+pub struct Ring<T: Copy, const CAPACITY: usize> {
+    entries: [Option<T>; CAPACITY],
+    head: usize,
+    length: usize,
+}
+
+impl<T: Copy, const CAPACITY: usize> Default for Ring<T, CAPACITY> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy, const CAPACITY: usize> Ring<T, CAPACITY> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: [None; CAPACITY],
+            head: 0,
+            length: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.length == CAPACITY
+    }
+
+    /// # Errors
+    ///
+    /// Returns the value back if the ring is full.
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+
+        self.entries[(self.head + self.length) % CAPACITY] = Some(value);
+        self.length += 1;
+
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let value = self.entries[self.head].take();
+        self.head = (self.head + 1) % CAPACITY;
+        self.length -= 1;
+
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_in_first_out() {
+        let mut ring = Ring::<u8, 4>::new();
+
+        assert_eq!(ring.push(1), Ok(()));
+        assert_eq!(ring.push(2), Ok(()));
+        assert_eq!(ring.push(3), Ok(()));
+
+        assert_eq!(ring.len(), 3);
+        assert_eq!(ring.pop(), Some(1));
+        assert_eq!(ring.pop(), Some(2));
+        assert_eq!(ring.pop(), Some(3));
+        assert_eq!(ring.pop(), None);
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn test_full() {
+        let mut ring = Ring::<u8, 2>::new();
+
+        assert_eq!(ring.push(1), Ok(()));
+        assert_eq!(ring.push(2), Ok(()));
+        assert!(ring.is_full());
+        assert_eq!(ring.push(3), Err(3));
+        assert_eq!(ring.pop(), Some(1));
+        assert_eq!(ring.push(3), Ok(()));
+        assert_eq!(ring.pop(), Some(2));
+        assert_eq!(ring.pop(), Some(3));
+    }
+
+    #[test]
+    fn test_wrap_around() {
+        let mut ring = Ring::<u16, 3>::new();
+
+        for value in 0..10 {
+            assert_eq!(ring.push(value), Ok(()));
+            assert_eq!(ring.pop(), Some(value));
+        }
+
+        assert!(ring.is_empty());
+    }
+}
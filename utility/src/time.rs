@@ -0,0 +1,298 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use core::fmt;
+
+pub const NANOSECONDS_PER_SECOND: u64 = 1_000_000_000;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+const DAYS_PER_ERA: i64 = 146_097;
+const EPOCH_DAYS: i64 = 719_468;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DateTime {
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    nanosecond: u32,
+}
+
+fn is_leap_year(year: u16) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * DAYS_PER_ERA + day_of_era - EPOCH_DAYS
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + EPOCH_DAYS;
+    let era = days.div_euclid(DAYS_PER_ERA);
+    let day_of_era = days - era * DAYS_PER_ERA;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+impl DateTime {
+    #[must_use]
+    pub fn new(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8) -> Option<Self> {
+        let valid = year >= 1970
+            && (1..=12).contains(&month)
+            && (1..=days_in_month(year, month)).contains(&day)
+            && hour < 24
+            && minute < 60
+            && second < 60;
+
+        valid.then_some(Self {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            nanosecond: 0,
+        })
+    }
+
+    pub const MAX: Self = Self {
+        year: u16::MAX,
+        month: 12,
+        day: 31,
+        hour: 23,
+        minute: 59,
+        second: 59,
+        nanosecond: 999_999_999,
+    };
+
+    #[must_use]
+    pub fn from_unix(seconds: u64, nanosecond: u32) -> Self {
+        let Ok(days) = i64::try_from(seconds / SECONDS_PER_DAY) else {
+            return Self::MAX;
+        };
+        let (year, month, day) = civil_from_days(days);
+        let Ok(year) = u16::try_from(year) else {
+            return Self::MAX;
+        };
+        let time = seconds % SECONDS_PER_DAY;
+
+        Self {
+            year,
+            month: u8::try_from(month).unwrap_or(u8::MAX),
+            day: u8::try_from(day).unwrap_or(u8::MAX),
+            hour: u8::try_from(time / 3600).unwrap_or(u8::MAX),
+            minute: u8::try_from(time / 60 % 60).unwrap_or(u8::MAX),
+            second: u8::try_from(time % 60).unwrap_or(u8::MAX),
+            nanosecond: nanosecond % 1_000_000_000,
+        }
+    }
+
+    fn days(&self) -> i64 {
+        days_from_civil(
+            i64::from(self.year),
+            i64::from(self.month),
+            i64::from(self.day),
+        )
+    }
+
+    #[must_use]
+    pub fn unix_seconds(&self) -> u64 {
+        let Ok(days) = u64::try_from(self.days()) else {
+            return 0;
+        };
+
+        days * SECONDS_PER_DAY
+            + u64::from(self.hour) * 3600
+            + u64::from(self.minute) * 60
+            + u64::from(self.second)
+    }
+
+    #[must_use]
+    pub fn with_nanosecond(mut self, nanosecond: u32) -> Self {
+        self.nanosecond = nanosecond % 1_000_000_000;
+        self
+    }
+
+    #[must_use]
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    #[must_use]
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    #[must_use]
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    #[must_use]
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    #[must_use]
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    #[must_use]
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    #[must_use]
+    pub fn nanosecond(&self) -> u32 {
+        self.nanosecond
+    }
+
+    #[must_use]
+    pub fn weekday(&self) -> Weekday {
+        match self.days().rem_euclid(7) {
+            0 => Weekday::Thursday,
+            1 => Weekday::Friday,
+            2 => Weekday::Saturday,
+            3 => Weekday::Sunday,
+            4 => Weekday::Monday,
+            5 => Weekday::Tuesday,
+            _ => Weekday::Wednesday,
+        }
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )?;
+
+        if let Some(precision) = formatter.precision().filter(|&precision| precision > 0) {
+            let precision = precision.min(9);
+            let divisor = 10u32.pow(9 - u32::try_from(precision).unwrap_or(9));
+            write!(
+                formatter,
+                ".{:0precision$}",
+                self.nanosecond / divisor,
+                precision = precision
+            )?;
+        }
+
+        write!(formatter, "Z")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::format;
+
+    #[test]
+    fn test_from_unix() {
+        let epoch = DateTime::from_unix(0, 0);
+        assert_eq!(epoch, DateTime::new(1970, 1, 1, 0, 0, 0).unwrap());
+
+        let date = DateTime::from_unix(951_782_400, 0);
+        assert_eq!((date.year(), date.month(), date.day()), (2000, 2, 29));
+
+        let date = DateTime::from_unix(1_735_689_599, 0);
+        assert_eq!(date, DateTime::new(2024, 12, 31, 23, 59, 59).unwrap());
+    }
+
+    #[test]
+    fn test_unix_seconds() {
+        for seconds in [0, 86_399, 951_782_400, 1_709_251_200, 4_102_444_800] {
+            assert_eq!(DateTime::from_unix(seconds, 0).unix_seconds(), seconds);
+        }
+    }
+
+    #[test]
+    fn test_saturation() {
+        assert_eq!(DateTime::from_unix(u64::MAX, 0), DateTime::MAX);
+        assert_eq!(DateTime::MAX.unix_seconds(), 2_005_949_145_599);
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(DateTime::new(2024, 2, 29, 0, 0, 0).is_some());
+        assert!(DateTime::new(2023, 2, 29, 0, 0, 0).is_none());
+        assert!(DateTime::new(1900, 2, 28, 0, 0, 0).is_none());
+        assert!(DateTime::new(2025, 13, 1, 0, 0, 0).is_none());
+        assert!(DateTime::new(2025, 4, 31, 0, 0, 0).is_none());
+        assert!(DateTime::new(2025, 1, 1, 24, 0, 0).is_none());
+    }
+
+    #[test]
+    fn test_weekday() {
+        assert_eq!(DateTime::from_unix(0, 0).weekday(), Weekday::Thursday);
+        let date = DateTime::new(2025, 1, 1, 12, 0, 0).unwrap();
+        assert_eq!(date.weekday(), Weekday::Wednesday);
+    }
+
+    #[test]
+    fn test_display() {
+        let date = DateTime::new(2025, 3, 4, 5, 6, 7)
+            .unwrap()
+            .with_nanosecond(123_456_789);
+        assert_eq!(format!("{date}"), "2025-03-04T05:06:07Z");
+        assert_eq!(format!("{date:.3}"), "2025-03-04T05:06:07.123Z");
+        assert_eq!(format!("{date:.9}"), "2025-03-04T05:06:07.123456789Z");
+    }
+}
@@ -0,0 +1,379 @@
+// Arcturus - Hobbyist operating system written in Rust.
+// Copyright (C) 2025 Theomund
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+pub const LEVELS: usize = 4;
+pub const SLOTS: usize = 64;
+
+const SLOT_BITS: u32 = 6;
+const LISTS: usize = LEVELS * SLOTS + 1;
+const EXPIRED: usize = LEVELS * SLOTS;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    Full,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Handle {
+    index: usize,
+    generation: u32,
+}
+
+struct Entry<T> {
+    deadline: u64,
+    value: Option<T>,
+    list: usize,
+    previous: Option<usize>,
+    next: Option<usize>,
+    generation: u32,
+}
+
+impl<T> Entry<T> {
+    const EMPTY: Self = Self {
+        deadline: 0,
+        value: None,
+        list: 0,
+        previous: None,
+        next: None,
+        generation: 0,
+    };
+}
+
+pub struct Wheel<T, const CAPACITY: usize> {
+    entries: [Entry<T>; CAPACITY],
+    lists: [Option<usize>; LISTS],
+    free: Option<usize>,
+    current: u64,
+    length: usize,
+}
+
+impl<T, const CAPACITY: usize> Default for Wheel<T, CAPACITY> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const CAPACITY: usize> Wheel<T, CAPACITY> {
+    #[must_use]
+    pub const fn new() -> Self {
+        let mut entries = [Entry::EMPTY; CAPACITY];
+        let mut index = 0;
+
+        while index + 1 < CAPACITY {
+            entries[index].next = Some(index + 1);
+            index += 1;
+        }
+
+        Self {
+            entries,
+            lists: [None; LISTS],
+            free: if CAPACITY > 0 { Some(0) } else { None },
+            current: 0,
+            length: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn current(&self) -> u64 {
+        self.current
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    fn list(&self, deadline: u64) -> usize {
+        if deadline <= self.current {
+            return EXPIRED;
+        }
+
+        let delta = deadline - self.current;
+
+        for level in 0..LEVELS {
+            let shift = SLOT_BITS * u32::try_from(level).expect("Failed to convert level.");
+
+            if level == LEVELS - 1 || delta < (SLOTS as u64) << shift {
+                let deadline = deadline.min(self.current + ((SLOTS as u64) << shift) - 1);
+                let slot = usize::try_from((deadline >> shift) % SLOTS as u64)
+                    .expect("Failed to convert slot.");
+
+                return level * SLOTS + slot;
+            }
+        }
+
+        unreachable!()
+    }
+
+    fn link(&mut self, index: usize, list: usize) {
+        let head = self.lists[list];
+
+        self.entries[index].list = list;
+        self.entries[index].previous = None;
+        self.entries[index].next = head;
+
+        if let Some(head) = head {
+            self.entries[head].previous = Some(index);
+        }
+
+        self.lists[list] = Some(index);
+    }
+
+    fn unlink(&mut self, index: usize) {
+        let Entry {
+            list,
+            previous,
+            next,
+            ..
+        } = self.entries[index];
+
+        match previous {
+            Some(previous) => self.entries[previous].next = next,
+            None => self.lists[list] = next,
+        }
+
+        if let Some(next) = next {
+            self.entries[next].previous = previous;
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the wheel is full.
+    pub fn insert(&mut self, deadline: u64, value: T) -> Result<Handle, Error> {
+        let index = self.free.ok_or(Error::Full)?;
+        self.free = self.entries[index].next;
+
+        self.entries[index].deadline = deadline;
+        self.entries[index].value = Some(value);
+        self.link(index, self.list(deadline));
+        self.length += 1;
+
+        Ok(Handle {
+            index,
+            generation: self.entries[index].generation,
+        })
+    }
+
+    fn release(&mut self, index: usize) -> Option<T> {
+        let value = self.entries[index].value.take();
+
+        self.entries[index].generation = self.entries[index].generation.wrapping_add(1);
+        self.entries[index].next = self.free;
+        self.free = Some(index);
+        self.length -= 1;
+
+        value
+    }
+
+    pub fn cancel(&mut self, handle: Handle) -> Option<T> {
+        let entry = self.entries.get(handle.index)?;
+
+        if entry.generation != handle.generation || entry.value.is_none() {
+            return None;
+        }
+
+        self.unlink(handle.index);
+        self.release(handle.index)
+    }
+
+    #[must_use]
+    pub fn next_deadline(&self) -> Option<u64> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.value.is_some())
+            .map(|entry| entry.deadline)
+            .min()
+    }
+
+    fn cascade(&mut self, list: usize) {
+        let mut next = self.lists[list].take();
+
+        while let Some(index) = next {
+            next = self.entries[index].next;
+            let deadline = self.entries[index].deadline;
+            self.link(index, self.list(deadline));
+        }
+    }
+
+    fn tick(&mut self) {
+        self.current += 1;
+
+        for level in (0..LEVELS).rev() {
+            let shift = SLOT_BITS * u32::try_from(level).expect("Failed to convert level.");
+
+            if self.current.trailing_zeros() >= shift {
+                let slot = usize::try_from((self.current >> shift) % SLOTS as u64)
+                    .expect("Failed to convert slot.");
+                self.cascade(level * SLOTS + slot);
+            }
+        }
+    }
+
+    fn skip(&mut self, now: u64) {
+        let empty = (0..LEVELS - 1)
+            .take_while(|&level| {
+                self.lists[level * SLOTS..(level + 1) * SLOTS]
+                    .iter()
+                    .all(Option::is_none)
+            })
+            .count();
+
+        if empty == 0 {
+            return;
+        }
+
+        let shift = SLOT_BITS * u32::try_from(empty).expect("Failed to convert level.");
+        let boundary = ((self.current >> shift) + 1) << shift;
+
+        self.current = self.current.max((boundary - 1).min(now));
+    }
+
+    pub fn poll(&mut self, now: u64) -> Option<T> {
+        loop {
+            if let Some(index) = self.lists[EXPIRED] {
+                self.unlink(index);
+                return self.release(index);
+            }
+
+            if self.current >= now || self.is_empty() {
+                self.current = self.current.max(now);
+                return None;
+            }
+
+            self.skip(now);
+
+            if self.current < now {
+                self.tick();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::vec::Vec;
+
+    fn drain<const CAPACITY: usize>(wheel: &mut Wheel<u64, CAPACITY>, now: u64) -> Vec<u64> {
+        let mut values = Vec::new();
+
+        while let Some(value) = wheel.poll(now) {
+            values.push(value);
+        }
+
+        values
+    }
+
+    #[test]
+    fn test_expire_in_order() {
+        let mut wheel = Wheel::<u64, 16>::new();
+
+        for deadline in [5, 1, 70, 4100, 64, 63] {
+            wheel.insert(deadline, deadline).unwrap();
+        }
+
+        assert_eq!(drain(&mut wheel, 0), [] as [u64; 0]);
+        assert_eq!(drain(&mut wheel, 4), [1]);
+        assert_eq!(drain(&mut wheel, 63), [5, 63]);
+        assert_eq!(drain(&mut wheel, 70), [64, 70]);
+        assert_eq!(drain(&mut wheel, 4099), [] as [u64; 0]);
+        assert_eq!(drain(&mut wheel, 4100), [4100]);
+        assert!(wheel.is_empty());
+    }
+
+    #[test]
+    fn test_every_deadline_fires_on_time() {
+        let mut wheel = Wheel::<u64, 256>::new();
+        let deadlines: Vec<u64> = (0..200).map(|index| index * 97 % 5_000 + 1).collect();
+
+        for &deadline in &deadlines {
+            wheel.insert(deadline, deadline).unwrap();
+        }
+
+        for now in 0..=5_000 {
+            for value in drain(&mut wheel, now) {
+                assert_eq!(value, now);
+            }
+        }
+
+        assert!(wheel.is_empty());
+    }
+
+    #[test]
+    fn test_far_deadline() {
+        let mut wheel = Wheel::<u64, 4>::new();
+        let deadline = (SLOTS as u64).pow(u32::try_from(LEVELS).unwrap()) * 3 + 17;
+        wheel.insert(deadline, deadline).unwrap();
+
+        assert_eq!(drain(&mut wheel, deadline - 1), [] as [u64; 0]);
+        assert_eq!(drain(&mut wheel, deadline), [deadline]);
+    }
+
+    #[test]
+    fn test_past_deadline() {
+        let mut wheel = Wheel::<u64, 4>::new();
+        assert_eq!(wheel.poll(100), None);
+
+        wheel.insert(50, 50).unwrap();
+        assert_eq!(wheel.poll(100), Some(50));
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut wheel = Wheel::<u64, 4>::new();
+        let first = wheel.insert(10, 1).unwrap();
+        let second = wheel.insert(10, 2).unwrap();
+
+        assert_eq!(wheel.cancel(first), Some(1));
+        assert_eq!(wheel.cancel(first), None);
+        assert_eq!(wheel.len(), 1);
+        assert_eq!(drain(&mut wheel, 10), [2]);
+        assert_eq!(wheel.cancel(second), None);
+
+        let third = wheel.insert(20, 3).unwrap();
+        assert_eq!(wheel.cancel(first), None);
+        assert_eq!(wheel.cancel(third), Some(3));
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut wheel = Wheel::<u64, 2>::new();
+        wheel.insert(1, 1).unwrap();
+        wheel.insert(2, 2).unwrap();
+        assert_eq!(wheel.insert(3, 3), Err(Error::Full));
+
+        assert_eq!(wheel.poll(1), Some(1));
+        assert!(wheel.insert(3, 3).is_ok());
+    }
+
+    #[test]
+    fn test_next_deadline() {
+        let mut wheel = Wheel::<u64, 4>::new();
+        assert_eq!(wheel.next_deadline(), None);
+
+        wheel.insert(300, 0).unwrap();
+        wheel.insert(20, 0).unwrap();
+        assert_eq!(wheel.next_deadline(), Some(20));
+    }
+}